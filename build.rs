@@ -0,0 +1,52 @@
+//! Generates the in-process solution registry used by the `registry` feature.
+//!
+//! Every `src/bin/NN.rs` solution is included into the library as a module, and listed in
+//! `SOLUTIONS` together with its day. See `src/template/registry.rs`.
+use std::{env, fmt::Write, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut days: Vec<u8> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let name = entry.file_name().into_string().ok()?;
+                    let stem = name.strip_suffix(".rs")?;
+                    if stem.len() != 2 {
+                        return None;
+                    }
+                    stem.parse().ok().filter(|day| (1..=25).contains(day))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    days.sort_unstable();
+
+    let mut registry = String::new();
+
+    for day in &days {
+        let path = bin_dir.join(format!("{day:02}.rs"));
+        writeln!(registry, "#[path = {:?}]", path.display().to_string()).unwrap();
+        writeln!(registry, "mod day_{day:02};").unwrap();
+    }
+
+    writeln!(registry, "\n/// All scaffolded solutions, sorted by day.").unwrap();
+    writeln!(registry, "pub const SOLUTIONS: &[Solution] = &[").unwrap();
+    for day in &days {
+        writeln!(
+            registry,
+            "    Solution {{ day: crate::day!({day}), run: day_{day:02}::run_solution }},"
+        )
+        .unwrap();
+    }
+    writeln!(registry, "];").unwrap();
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("registry.rs"), registry).unwrap();
+}
@@ -28,6 +28,7 @@ impl OrderingRules {
     }
 
     /// Returns true if placing `first` before `second` violates any rule
+    #[allow(clippy::unnecessary_map_or)]
     fn violates_rule(&self, first: u32, second: u32) -> bool {
         self.successors
             .get(&first)
@@ -45,6 +46,7 @@ impl OrderingRules {
     }
 
     /// Compares two pages according to the ordering rules
+    #[allow(clippy::unnecessary_map_or)]
     fn compare_pages(&self, a: u32, b: u32) -> std::cmp::Ordering {
         use std::cmp::Ordering;
 
@@ -143,14 +145,12 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(143));
+        advent_of_code::template::examples::check(DAY, 1, part_one);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(123));
+        advent_of_code::template::examples::check(DAY, 2, part_two);
     }
 
     #[test]
@@ -1,7 +1,7 @@
 use std::collections::HashSet;
 use std::fmt;
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(6, parse = parse_input);
 
 fn parse_input(input: &str) -> MapState {
     let mut map = Vec::new();
@@ -71,7 +71,7 @@ struct Position {
 #[derive(Clone)]
 struct Map(Vec<Vec<char>>);
 
-struct MapState {
+pub struct MapState {
     map: Map,
     position: Position,
     direction: Direction,
@@ -233,15 +233,13 @@ fn creates_loop(state: &MapState, obstacle_x: usize, obstacle_y: usize) -> bool
     }
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let state = parse_input(input);
-    let count = patrol(&state);
+pub fn part_one(state: &MapState) -> Option<u64> {
+    let count = patrol(state);
     Some(count)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let state = parse_input(input);
-    let patrol_positions = get_patrol_path(&state);
+pub fn part_two(state: &MapState) -> Option<u64> {
+    let patrol_positions = get_patrol_path(state);
     let mut count = 0;
 
     // Only test positions the guard actually visits (excluding start)
@@ -251,7 +249,7 @@ pub fn part_two(input: &str) -> Option<u64> {
         }
 
         // Check if placing obstacle here creates a loop
-        if creates_loop(&state, pos.x, pos.y) {
+        if creates_loop(state, pos.x, pos.y) {
             count += 1;
         }
     }
@@ -265,13 +263,11 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(41));
+        advent_of_code::template::examples::check(DAY, 1, |input| part_one(&parse_input(input)));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6));
+        advent_of_code::template::examples::check(DAY, 2, |input| part_two(&parse_input(input)));
     }
 }
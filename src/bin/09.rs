@@ -147,6 +147,7 @@ impl Disk {
         None
     }
 
+    #[allow(clippy::needless_range_loop)]
     fn to_blocks(&self) -> Vec<Option<u32>> {
         let mut blocks = vec![None; self.total_size];
         for file in &self.files {
@@ -157,6 +158,7 @@ impl Disk {
         blocks
     }
 
+    #[allow(clippy::wrong_self_convention)]
     fn from_blocks(&mut self, blocks: &[Option<u32>]) {
         self.files.clear();
         self.free_spaces.clear();
@@ -208,14 +210,12 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1928));
+        advent_of_code::template::examples::check(DAY, 1, part_one);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2858));
+        advent_of_code::template::examples::check(DAY, 2, part_two);
     }
 
     #[test]
@@ -226,6 +226,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::identity_op, clippy::erasing_op)]
     fn test_checksum_calculation() {
         let disk = Disk::from_input("12345");
         // Input "12345" creates: file 0 (size 1), space (size 2), file 1 (size 3), space (size 4), file 2 (size 5)
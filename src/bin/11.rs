@@ -2,6 +2,7 @@ advent_of_code::solution!(11);
 
 use std::collections::HashMap;
 
+#[allow(clippy::trim_split_whitespace)]
 fn parse_input(input: &str) -> Vec<u64> {
     input
         .trim()
@@ -29,6 +30,7 @@ fn split_number(n: u64, digits: u32) -> (u64, u64) {
     (n / divisor, n % divisor)
 }
 
+#[allow(clippy::manual_is_multiple_of)]
 fn count_stones_after_blinks(stone: u64, blinks: u32, memo: &mut HashMap<(u64, u32), u64>) -> u64 {
     if blinks == 0 {
         return 1;
@@ -80,13 +82,11 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(55312));
+        advent_of_code::template::examples::check(DAY, 1, part_one);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(65601038650482));
+        advent_of_code::template::examples::check(DAY, 2, part_two);
     }
 }
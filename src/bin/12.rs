@@ -1,4 +1,4 @@
-advent_of_code::solution!(12);
+advent_of_code::solution!(12, parse = parse_input);
 
 use std::collections::HashSet;
 
@@ -139,9 +139,8 @@ fn find_all_regions(grid: &Grid) -> Vec<Region> {
     regions
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let grid = parse_input(input);
-    let regions = find_all_regions(&grid);
+pub fn part_one(grid: &Grid) -> Option<u64> {
+    let regions = find_all_regions(grid);
 
     let total_price = regions
         .iter()
@@ -151,9 +150,8 @@ pub fn part_one(input: &str) -> Option<u64> {
     Some(total_price)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let grid = parse_input(input);
-    let regions = find_all_regions(&grid);
+pub fn part_two(grid: &Grid) -> Option<u64> {
+    let regions = find_all_regions(grid);
 
     let total_price = regions
         .iter()
@@ -169,13 +167,11 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1930));
+        advent_of_code::template::examples::check(DAY, 1, |input| part_one(&parse_input(input)));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1206));
+        advent_of_code::template::examples::check(DAY, 2, |input| part_two(&parse_input(input)));
     }
 }
@@ -45,7 +45,7 @@
 //! helped me understand concepts like linear algebra, number theory, and algorithmic thinking
 //! that I hadn't encountered before.
 
-advent_of_code::solution!(13);
+advent_of_code::solution!(13, parse = parse_input);
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 struct Coord {
@@ -53,8 +53,8 @@ struct Coord {
     y: i128,
 }
 
-#[derive(Debug)]
-struct Machine {
+#[derive(Debug, Clone, Copy)]
+pub struct Machine {
     a: Coord,
     b: Coord,
     prize: Coord,
@@ -317,6 +317,7 @@ fn solve_colinear(
     // Cost as a function of k: C(k) = c_a*(n_a0 + k*t) + c_b*(n_b0 - k*s)
     // => C(k) = C0 + k*(c_a*t - c_b*s). Monotonic in k.
     let slope = config.cost_a * t - config.cost_b * s;
+    #[allow(clippy::if_same_then_else)]
     let k_star = if slope < 0 {
         k_max
     } else if slope > 0 {
@@ -341,8 +342,7 @@ fn solve_colinear(
 }
 
 /// Solves Part 1: Find minimum tokens with 100-press limit per machine
-pub fn part_one(input: &str) -> Option<u64> {
-    let machines = parse_input(input);
+pub fn part_one(machines: &[Machine]) -> Option<u64> {
     let config = SolverConfig::part_one();
     let total: u64 = machines
         .iter()
@@ -352,20 +352,21 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 /// Solves Part 2: Find minimum tokens with unlimited presses and offset applied
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut machines = parse_input(input);
-
+pub fn part_two(machines: &[Machine]) -> Option<u64> {
     // Add 10000000000000 to both X and Y coordinates of every prize
     const OFFSET: i128 = 10000000000000;
-    for machine in &mut machines {
-        machine.prize.x += OFFSET;
-        machine.prize.y += OFFSET;
-    }
 
     let config = SolverConfig::part_two();
     let total: u64 = machines
         .iter()
-        .filter_map(|machine| solve_machine(machine, config))
+        .map(|machine| Machine {
+            prize: Coord {
+                x: machine.prize.x + OFFSET,
+                y: machine.prize.y + OFFSET,
+            },
+            ..*machine
+        })
+        .filter_map(|machine| solve_machine(&machine, config))
         .sum();
     Some(total)
 }
@@ -376,13 +377,12 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(480));
+        advent_of_code::template::examples::check(DAY, 1, |input| part_one(&parse_input(input)));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(875318608908)); // Only machines 2 and 4 are solvable in Part 2
+        // only machines 2 and 4 of the example are solvable in part two.
+        advent_of_code::template::examples::check(DAY, 2, |input| part_two(&parse_input(input)));
     }
 }
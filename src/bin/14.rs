@@ -1,4 +1,4 @@
-advent_of_code::solution!(14);
+advent_of_code::solution!(14, parse = parse_input);
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 struct Coord {
@@ -7,7 +7,7 @@ struct Coord {
 }
 
 #[derive(Debug, Clone)]
-struct Robot {
+pub struct Robot {
     position: Coord,
     velocity: Coord,
 }
@@ -98,6 +98,7 @@ fn quandrant_predict_position(coords: &Vec<Coord>, config: &MapConfig) -> [u64;
 
 /// Check if the robot positions form a Christmas tree pattern
 /// Looks for more than 5 successive rows with more than 6 consecutive occupied cells
+#[allow(clippy::unnecessary_map_or)]
 fn is_christmas_tree_pattern(coords: &[Coord], config: &MapConfig) -> bool {
     use std::collections::HashMap;
     
@@ -174,9 +175,8 @@ fn print_map(coords: &[Coord], config: &MapConfig) {
 }
 
 
-pub fn part_one(input: &str) -> Option<u64> {
+pub fn part_one(robots: &[Robot]) -> Option<u64> {
     let config = get_map_config();
-    let robots = parse_input(input);
 
     // Simulate for 100 seconds
     let positions: Vec<Coord> = robots
@@ -190,9 +190,8 @@ pub fn part_one(input: &str) -> Option<u64> {
     Some(quadrant_counts.iter().product())
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
+pub fn part_two(robots: &[Robot]) -> Option<u64> {
     let config = get_map_config();
-    let robots = parse_input(input);
 
     // Find the first time when robots form a Christmas tree pattern
     for time in 1.. {
@@ -216,8 +215,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(12));
+        advent_of_code::template::examples::check(DAY, 1, |input| part_one(&parse_input(input)));
     }
 
     #[test]
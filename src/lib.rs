@@ -1,3 +1,7 @@
 pub mod template;
 
+// Solutions refer to the library by name, which needs to resolve when they are compiled into the registry.
+#[cfg(feature = "registry")]
+extern crate self as advent_of_code;
+
 // Use this file to add helper functions and additional modules.
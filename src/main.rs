@@ -1,4 +1,4 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{all, download, examples, read, scaffold, solve, time};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -7,34 +7,64 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        commands::{
+            examples::BlockSelection,
+            time::{CompareOptions, MergePolicy, Selection},
+        },
+        runner::BenchOptions,
+        Day, InputSource,
+    };
+    use std::{process, time::Duration};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            /// `None` downloads all missing inputs.
+            day: Option<Day>,
+            overwrite: bool,
+            delay: Duration,
         },
         Read {
             day: Day,
         },
+        Examples {
+            day: Day,
+            list: bool,
+            selection: BlockSelection,
+            overwrite: bool,
+        },
         Scaffold {
             day: Day,
             download: bool,
             overwrite: bool,
+            dry_run: bool,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            record: Option<u8>,
+            time: Option<BenchOptions>,
+            input: InputSource,
+            all_inputs: bool,
         },
         All {
             release: bool,
+            dhat: bool,
+            in_process: bool,
+            timeout: Option<Duration>,
         },
         Time {
-            all: bool,
-            day: Option<Day>,
-            store: bool,
+            selection: Selection,
+            /// `None` if the timings should not be stored.
+            store: Option<MergePolicy>,
+            compare: Option<CompareOptions>,
+            /// Record heap usage with the counting allocator.
+            memory: bool,
+            in_process: bool,
+            bench: BenchOptions,
+            timeout: Option<Duration>,
         },
         #[cfg(feature = "today")]
         Today,
@@ -46,34 +76,133 @@ mod args {
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                dhat: args.contains("--dhat"),
+                in_process: args.contains("--in-process"),
+                timeout: parse_timeout(&mut args)?,
             },
             Some("time") => {
                 let all = args.contains("--all");
-                let store = args.contains("--store");
+                let changed = args.contains("--changed");
+                let store = parse_store(&mut args)?;
+                let compare = parse_compare_options(&mut args)?;
+                let memory = args.contains("--memory");
+                let in_process = args.contains("--in-process");
+                let bench = parse_bench_options(&mut args)?;
+                let timeout = parse_timeout(&mut args)?;
+                let day = args.opt_free_from_str()?;
+
+                if changed && (all || day.is_some()) {
+                    return Err("--changed can not be combined with a day or --all.".into());
+                }
+
+                if memory && compare.is_some() {
+                    return Err("--memory can not be combined with --compare.".into());
+                }
+
+                if memory && in_process {
+                    return Err("--memory can not be combined with --in-process.".into());
+                }
+
+                let selection = match day {
+                    Some(day) => Selection::Day(day),
+                    None if all => Selection::All,
+                    None if changed => Selection::Changed,
+                    None => Selection::Incomplete,
+                };
 
                 AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
+                    selection,
                     store,
+                    compare,
+                    memory,
+                    in_process,
+                    bench,
+                    timeout,
+                }
+            }
+            Some("download") => {
+                let missing = args.contains("--missing");
+                let overwrite = args.contains("--overwrite");
+                let delay: Option<f64> = args.opt_value_from_str("--delay")?;
+                let day = args.opt_free_from_str()?;
+
+                if day.is_some() == missing {
+                    return Err("Pass either a day or --missing.".into());
+                }
+
+                AppArguments::Download {
+                    day,
+                    overwrite,
+                    delay: Duration::try_from_secs_f64(delay.unwrap_or(3.0))?,
                 }
             }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
-            },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
+            Some("examples") => AppArguments::Examples {
+                list: args.contains("--list"),
+                selection: BlockSelection {
+                    shared: args.opt_value_from_str("--block")?,
+                    parts: [
+                        args.opt_value_from_str("--part-one-block")?,
+                        args.opt_value_from_str("--part-two-block")?,
+                    ],
+                    interactive: args.contains("--interactive"),
+                },
                 overwrite: args.contains("--overwrite"),
-            },
-            Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
             },
+            Some("scaffold") => {
+                let download = args.contains("--download");
+                let overwrite = args.contains("--overwrite");
+                let dry_run = args.contains("--dry-run");
+
+                if download && dry_run {
+                    return Err("--dry-run can not be combined with --download.".into());
+                }
+
+                AppArguments::Scaffold {
+                    day: args.free_from_str()?,
+                    download,
+                    overwrite,
+                    dry_run,
+                }
+            }
+            Some("solve") => {
+                let time = args.contains("--time");
+                let bench = parse_bench_options(&mut args)?;
+                let input = parse_input_source(&mut args)?;
+                let all_inputs = args.contains("--all-inputs");
+                let submit = args.opt_value_from_str("--submit")?;
+                let record = args.opt_value_from_str("--record")?;
+
+                if all_inputs
+                    && (input != InputSource::Puzzle || submit.is_some() || record.is_some())
+                {
+                    return Err("--all-inputs can not be combined with other input options, --submit or --record.".into());
+                }
+
+                if submit.is_some() && input != InputSource::Puzzle {
+                    return Err("--submit can only be used with the puzzle input.".into());
+                }
+
+                if record.is_some() && !input.has_stored_answers() {
+                    return Err(
+                        "--record can only be used with the puzzle input or a named input.".into(),
+                    );
+                }
+
+                AppArguments::Solve {
+                    day: args.free_from_str()?,
+                    release: args.contains("--release"),
+                    submit,
+                    record,
+                    dhat: args.contains("--dhat"),
+                    time: time.then_some(bench),
+                    input,
+                    all_inputs,
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -93,6 +222,69 @@ mod args {
 
         Ok(app_args)
     }
+
+    fn parse_bench_options(
+        args: &mut pico_args::Arguments,
+    ) -> Result<BenchOptions, Box<dyn std::error::Error>> {
+        let options = BenchOptions::new(
+            args.opt_value_from_str("--budget")?,
+            args.opt_value_from_str("--min-samples")?,
+            args.opt_value_from_str("--max-samples")?,
+            args.opt_value_from_str("--warmup")?,
+        )?;
+        Ok(options)
+    }
+
+    /// Parse `--store` with its optional `--policy <latest|best|median[:<runs>]>`.
+    fn parse_store(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<MergePolicy>, Box<dyn std::error::Error>> {
+        let store = args.contains("--store");
+        let policy: Option<MergePolicy> = args.opt_value_from_str("--policy")?;
+
+        if !store && policy.is_some() {
+            return Err("--policy requires --store.".into());
+        }
+
+        Ok(store.then(|| policy.unwrap_or_default()))
+    }
+
+    /// Parse `--compare` with its optional `--baseline <commit>` and `--threshold <percent>`.
+    fn parse_compare_options(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<CompareOptions>, Box<dyn std::error::Error>> {
+        let compare = args.contains("--compare");
+        let baseline = args.opt_value_from_str("--baseline")?;
+        let threshold = args.opt_value_from_str("--threshold")?;
+
+        if !compare && (baseline.is_some() || threshold.is_some()) {
+            return Err("--baseline and --threshold require --compare.".into());
+        }
+
+        Ok(compare.then(|| CompareOptions {
+            baseline,
+            threshold: threshold.unwrap_or(10.0),
+        }))
+    }
+
+    /// Parse `--input <path>` (or `-` for stdin), `--input-name <name>` and `--example`, the input a solution runs against.
+    fn parse_input_source(
+        args: &mut pico_args::Arguments,
+    ) -> Result<InputSource, Box<dyn std::error::Error>> {
+        Ok(InputSource::new(
+            args.opt_value_from_str("--input")?,
+            args.opt_value_from_str("--input-name")?,
+            args.contains("--example"),
+        )?)
+    }
+
+    /// Parse `--timeout <seconds>`, the time after which a single day's solution is killed.
+    fn parse_timeout(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Duration>, Box<dyn std::error::Error>> {
+        let timeout: Option<f64> = args.opt_value_from_str("--timeout")?;
+        Ok(timeout.map(Duration::try_from_secs_f64).transpose()?)
+    }
 }
 
 fn main() {
@@ -102,18 +294,53 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
+            AppArguments::All {
+                release,
+                dhat,
+                in_process,
+                timeout,
+            } => all::handle(release, dhat, in_process, timeout),
+            AppArguments::Time {
+                selection,
+                store,
+                compare,
+                memory,
+                in_process,
+                bench,
+                timeout,
+            } => time::handle(
+                selection,
+                store,
+                compare.as_ref(),
+                memory,
+                in_process,
+                &bench,
+                timeout,
+            ),
+            AppArguments::Download {
+                day,
+                overwrite,
+                delay,
+            } => match day {
+                Some(day) => download::handle(day, overwrite),
+                None => download::handle_missing(overwrite, delay),
+            },
             AppArguments::Read { day } => read::handle(day),
+            AppArguments::Examples {
+                day,
+                list,
+                selection,
+                overwrite,
+            } => examples::handle(day, list, &selection, overwrite),
             AppArguments::Scaffold {
                 day,
                 download,
                 overwrite,
+                dry_run,
             } => {
-                scaffold::handle(day, overwrite);
+                scaffold::handle(day, overwrite, dry_run);
                 if download {
-                    download::handle(day);
+                    download::handle(day, false);
                 }
             }
             AppArguments::Solve {
@@ -121,13 +348,23 @@ fn main() {
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                record,
+                time,
+                input,
+                all_inputs,
+            } => {
+                if all_inputs {
+                    solve::handle_all_inputs(day, release, dhat, time.as_ref());
+                } else {
+                    solve::handle(day, release, dhat, submit, record, time.as_ref(), &input);
+                }
+            }
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
+                        scaffold::handle(day, false, false);
+                        download::handle(day, false);
                         read::handle(day)
                     }
                     None => {
@@ -0,0 +1,214 @@
+/// Lightweight heap accounting for the `count-alloc` feature.
+///
+/// With the feature enabled, `solution!` installs [`CountingAlloc`] as the global allocator of
+/// every solution binary. It forwards to the system allocator and keeps a few atomic counters,
+/// which the runner reads to attribute allocations to the parse step and each part.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use tinyjson::JsonValue;
+
+#[cfg(all(feature = "count-alloc", feature = "dhat-heap"))]
+compile_error!("the `count-alloc` and `dhat-heap` features cannot be combined: both install a global allocator.");
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static TOTAL_BYTES: AtomicU64 = AtomicU64::new(0);
+static CURRENT_BYTES: AtomicU64 = AtomicU64::new(0);
+static PEAK_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Global allocator that counts allocations and tracks the peak heap size.
+pub struct CountingAlloc;
+
+impl CountingAlloc {
+    fn record_alloc(size: usize) {
+        let size = size as u64;
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        TOTAL_BYTES.fetch_add(size, Ordering::Relaxed);
+        let current = CURRENT_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK_BYTES.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn record_dealloc(size: usize) {
+        CURRENT_BYTES.fetch_sub(size as u64, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            // a reallocation counts as a new allocation of `new_size` that frees the old block.
+            Self::record_dealloc(layout.size());
+            Self::record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Heap usage of a single run of a part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations, including reallocations.
+    pub allocations: u64,
+    /// Sum of the sizes of all allocations in bytes.
+    pub total_bytes: u64,
+    /// Largest growth of the heap over its size at the start of the run in bytes.
+    pub peak_bytes: u64,
+}
+
+/// Run `func` and measure its heap usage.
+/// Returns `None` for the stats if the binary was not built with the `count-alloc` feature.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !cfg!(feature = "count-alloc") {
+        return (func(), None);
+    }
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let total_bytes = TOTAL_BYTES.load(Ordering::Relaxed);
+    let baseline = CURRENT_BYTES.load(Ordering::Relaxed);
+    PEAK_BYTES.store(baseline, Ordering::Relaxed);
+
+    let result = func();
+
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        total_bytes: TOTAL_BYTES.load(Ordering::Relaxed) - total_bytes,
+        peak_bytes: PEAK_BYTES.load(Ordering::Relaxed).saturating_sub(baseline),
+    };
+
+    (result, Some(stats))
+}
+
+/// Format a number of bytes with a binary unit, e.g. `1.5 KiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&AllocStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &AllocStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
+        map.insert(
+            "total_bytes".into(),
+            JsonValue::Number(value.total_bytes as f64),
+        );
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for AllocStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected alloc stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected alloc.{key} to be a number."))
+        };
+
+        Ok(AllocStats {
+            allocations: number("allocations")? as u64,
+            total_bytes: number("total_bytes")? as u64,
+            peak_bytes: number("peak_bytes")? as u64,
+        })
+    }
+}
+
+/// Read optional alloc stats, treating a missing key the same as `null`.
+pub fn optional_from_json(
+    json: &HashMap<String, JsonValue>,
+    key: &str,
+) -> Result<Option<AllocStats>, String> {
+    match json.get(key) {
+        None => Ok(None),
+        Some(v) if v.is_null() => Ok(None),
+        Some(v) => AllocStats::try_from(v).map(Some),
+    }
+}
+
+/// Serialize optional alloc stats, using `null` for `None`.
+pub fn optional_to_json(stats: Option<&AllocStats>) -> JsonValue {
+    stats.map_or(JsonValue::Null, JsonValue::from)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, AllocStats};
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let stats = AllocStats {
+            allocations: 12,
+            total_bytes: 4096,
+            peak_bytes: 1024,
+        };
+        let json = JsonValue::from(&stats);
+        assert_eq!(AllocStats::try_from(&json).unwrap(), stats);
+    }
+}
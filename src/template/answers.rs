@@ -0,0 +1,342 @@
+/// Known-correct answers for the real puzzle inputs, used to catch regressions.
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{timings::to_sorted_string, Day};
+
+static ANSWERS_FILE_PATH: &str = "./data/answers.json";
+
+/// Result of comparing an answer against the stored answer of a part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Check {
+    /// The answer matches the stored answer.
+    Correct,
+    /// The answer differs from the stored answer.
+    Wrong { expected: String },
+    /// No answer is stored for this part.
+    Unknown,
+}
+
+/// Stored answers, keyed by day and part.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    /// Answers for the puzzle input at `data/inputs/<day>.txt`.
+    pub data: BTreeMap<Day, [Option<String>; 2]>,
+    /// Answers for the named inputs at `data/inputs/<day>/<name>.txt`, keyed by name.
+    pub named: BTreeMap<Day, BTreeMap<String, [Option<String>; 2]>>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        fs::write(ANSWERS_FILE_PATH, to_sorted_string(&JsonValue::from(self)))
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    /// Fails if the file exists but can not be read or parsed, so that it is never overwritten.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(ANSWERS_FILE_PATH) {
+            Ok(json) => {
+                Answers::try_from(json).map_err(|e| format!("invalid {ANSWERS_FILE_PATH}: {e}"))
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("could not read {ANSWERS_FILE_PATH}: {e}.")),
+        }
+    }
+
+    /// Get the stored answer of a part. `input` is the name of a named input, or `None` for the puzzle input.
+    pub fn get(&self, day: Day, input: Option<&str>, part: u8) -> Option<&str> {
+        let parts = match input {
+            None => self.data.get(&day)?,
+            Some(name) => self.named.get(&day)?.get(name)?,
+        };
+        parts.get(part_index(part)?)?.as_deref()
+    }
+
+    /// Store the answer of a part, replacing any previous answer.
+    pub fn set(&mut self, day: Day, input: Option<&str>, part: u8, answer: &str) {
+        let Some(index) = part_index(part) else {
+            return;
+        };
+
+        let parts = match input {
+            None => self.data.entry(day).or_default(),
+            Some(name) => self
+                .named
+                .entry(day)
+                .or_default()
+                .entry(name.to_string())
+                .or_default(),
+        };
+
+        parts[index] = Some(answer.to_string());
+    }
+
+    /// Store the answers confirmed on the puzzle page of a day. Returns the parts whose answer changed.
+    pub fn merge_confirmed(&mut self, day: Day, confirmed: &[Option<String>; 2]) -> Vec<u8> {
+        let mut changed = vec![];
+
+        for (part, answer) in (1..).zip(confirmed) {
+            let Some(answer) = answer else {
+                continue;
+            };
+
+            if self.get(day, None, part) != Some(answer) {
+                self.set(day, None, part, answer);
+                changed.push(part);
+            }
+        }
+
+        changed
+    }
+
+    /// Compare an answer against the stored answer of a part.
+    pub fn check(&self, day: Day, input: Option<&str>, part: u8, answer: &str) -> Check {
+        match self.get(day, input, part) {
+            Some(expected) if expected == answer => Check::Correct,
+            Some(expected) => Check::Wrong {
+                expected: expected.to_string(),
+            },
+            None => Check::Unknown,
+        }
+    }
+}
+
+fn part_index(part: u8) -> Option<usize> {
+    match part {
+        1 | 2 => Some(usize::from(part - 1)),
+        _ => None,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answers> for JsonValue {
+    fn from(value: &Answers) -> Self {
+        let days: BTreeSet<&Day> = value.data.keys().chain(value.named.keys()).collect();
+
+        let map: HashMap<String, JsonValue> = days
+            .into_iter()
+            .map(|day| {
+                let mut day_map = match value.data.get(day) {
+                    Some(parts) => parts_to_json(parts),
+                    None => HashMap::new(),
+                };
+
+                if let Some(named) = value.named.get(day) {
+                    let inputs: HashMap<String, JsonValue> = named
+                        .iter()
+                        .map(|(name, parts)| {
+                            (name.clone(), JsonValue::Object(parts_to_json(parts)))
+                        })
+                        .collect();
+                    day_map.insert("inputs".into(), JsonValue::Object(inputs));
+                }
+
+                (day.to_string(), JsonValue::Object(day_map))
+            })
+            .collect();
+
+        JsonValue::Object(map)
+    }
+}
+
+fn parts_to_json(parts: &[Option<String>; 2]) -> HashMap<String, JsonValue> {
+    parts
+        .iter()
+        .enumerate()
+        .map(|(i, answer)| {
+            (
+                format!("part_{}", i + 1),
+                match answer {
+                    Some(x) => JsonValue::String(x.clone()),
+                    None => JsonValue::Null,
+                },
+            )
+        })
+        .collect()
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_days = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let mut answers = Answers::default();
+
+        for (key, value) in json_days {
+            let day = Day::from_str(key).map_err(|_| format!("`{key}` is not a valid day."))?;
+
+            let json_parts = value
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or(format!("expected answers of day {day} to be an object."))?;
+
+            let has_parts = json_parts.keys().any(|k| k.starts_with("part_"));
+            if has_parts {
+                answers
+                    .data
+                    .insert(day, parts_from_json(json_parts, &day.to_string())?);
+            }
+
+            match json_parts.get("inputs") {
+                None => {}
+                Some(v) if v.is_null() => {}
+                Some(v) => {
+                    let json_inputs = v
+                        .get::<HashMap<String, JsonValue>>()
+                        .ok_or(format!("expected {day}.inputs to be an object."))?;
+
+                    for (name, value) in json_inputs {
+                        let context = format!("{day}.inputs.{name}");
+                        let json_parts = value
+                            .get::<HashMap<String, JsonValue>>()
+                            .ok_or(format!("expected {context} to be an object."))?;
+
+                        answers
+                            .named
+                            .entry(day)
+                            .or_default()
+                            .insert(name.clone(), parts_from_json(json_parts, &context)?);
+                    }
+                }
+            }
+        }
+
+        Ok(answers)
+    }
+}
+
+fn parts_from_json(
+    json_parts: &HashMap<String, JsonValue>,
+    context: &str,
+) -> Result<[Option<String>; 2], String> {
+    let mut parts: [Option<String>; 2] = [None, None];
+
+    for (i, part) in parts.iter_mut().enumerate() {
+        let key = format!("part_{}", i + 1);
+        *part = match json_parts.get(&key) {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or(format!("expected {context}.{key} to be null or string."))?
+                    .clone(),
+            ),
+        };
+    }
+
+    Ok(parts)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answers, Check};
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    fn get_mock_answers() -> Answers {
+        let mut answers = Answers::default();
+        answers.set(day!(6), None, 1, "41");
+        answers.set(day!(6), None, 2, "6");
+        answers.set(day!(7), None, 1, "3749");
+        answers.set(day!(7), Some("alice"), 1, "3750");
+        answers
+    }
+
+    #[test]
+    fn checks_answers() {
+        let answers = get_mock_answers();
+        assert_eq!(answers.check(day!(6), None, 1, "41"), Check::Correct);
+        assert_eq!(
+            answers.check(day!(6), None, 2, "7"),
+            Check::Wrong {
+                expected: "6".into()
+            }
+        );
+        assert_eq!(answers.check(day!(7), None, 2, "11387"), Check::Unknown);
+        assert_eq!(answers.check(day!(8), None, 1, "14"), Check::Unknown);
+    }
+
+    #[test]
+    fn ignores_invalid_parts() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), None, 3, "1");
+        assert_eq!(answers, Answers::default());
+        assert_eq!(answers.get(day!(1), None, 0), None);
+    }
+
+    #[test]
+    fn merges_confirmed_answers() {
+        let mut answers = get_mock_answers();
+        let changed = answers.merge_confirmed(day!(6), &[Some("41".into()), Some("7".into())]);
+        assert_eq!(changed, vec![2]);
+        assert_eq!(answers.get(day!(6), None, 2), Some("7"));
+
+        let changed = answers.merge_confirmed(day!(8), &[Some("14".into()), None]);
+        assert_eq!(changed, vec![1]);
+        assert_eq!(answers.get(day!(8), None, 2), None);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let answers = get_mock_answers();
+        let json = JsonValue::from(&answers).stringify().unwrap();
+        assert_eq!(Answers::try_from(json).unwrap(), answers);
+    }
+
+    #[test]
+    fn handles_partial_json() {
+        let json = r#"{ "01": { "part_1": "11" } }"#.to_string();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.get(day!(1), None, 1), Some("11"));
+        assert_eq!(answers.get(day!(1), None, 2), None);
+    }
+
+    #[test]
+    fn checks_answers_per_input() {
+        let answers = get_mock_answers();
+        assert_eq!(
+            answers.check(day!(7), Some("alice"), 1, "3750"),
+            Check::Correct
+        );
+        assert_eq!(
+            answers.check(day!(7), Some("alice"), 1, "3749"),
+            Check::Wrong {
+                expected: "3750".into()
+            }
+        );
+        assert_eq!(
+            answers.check(day!(7), Some("bob"), 1, "3749"),
+            Check::Unknown
+        );
+    }
+
+    #[test]
+    fn handles_named_inputs_json() {
+        let json = r#"{ "02": { "inputs": { "bob": { "part_2": "4" } } } }"#.to_string();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.get(day!(2), Some("bob"), 2), Some("4"));
+        assert_eq!(answers.get(day!(2), None, 2), None);
+        assert!(answers.data.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_days() {
+        let json = r#"{ "26": { "part_1": "11" } }"#.to_string();
+        Answers::try_from(json).unwrap();
+    }
+}
@@ -1,7 +1,9 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
     fmt::Display,
+    io::{self, BufRead, BufReader, Read, Write},
     process::{Command, Output, Stdio},
+    thread,
 };
 
 use crate::template::Day;
@@ -10,21 +12,135 @@ use crate::template::Day;
 pub enum AocCommandError {
     CommandNotFound,
     CommandNotCallable,
+    /// No session cookie file was found.
+    MissingSession,
+    /// The session cookie was rejected.
+    InvalidSession,
+    /// The day or year is not a valid Advent of Code date.
+    InvalidDate,
+    /// The puzzle of this day is not unlocked yet.
+    NotUnlocked,
+    /// Advent of Code asked to slow down.
+    Throttled,
+    /// The Advent of Code website could not be reached.
+    Network,
+    /// aoc-cli failed for a reason that could not be classified.
     BadExitStatus(Output),
 }
 
+impl AocCommandError {
+    /// Classify a failed call by the output of aoc-cli.
+    fn from_output(output: Output) -> Self {
+        let text = format!(
+            "{}\n{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        Self::classify(&text).unwrap_or(AocCommandError::BadExitStatus(output))
+    }
+
+    /// Recognize common failures in the output of aoc-cli.
+    /// Only error lines are considered, so puzzle text or answers that contain a pattern are not misread.
+    fn classify(text: &str) -> Option<Self> {
+        let errors: Vec<String> = text
+            .lines()
+            .map(|line| line.trim().to_lowercase())
+            .filter(|line| line.starts_with("[error") || line.starts_with("error:"))
+            .collect();
+        let has = |patterns: &[&str]| {
+            errors
+                .iter()
+                .any(|line| patterns.iter().any(|p| line.contains(p)))
+        };
+
+        if has(&[
+            "session cookie file not found",
+            "failed to read session cookie",
+        ]) {
+            Some(AocCommandError::MissingSession)
+        } else if has(&["invalid session cookie", "please log in"]) {
+            Some(AocCommandError::InvalidSession)
+        } else if has(&["still locked", "before it unlocks"]) {
+            Some(AocCommandError::NotUnlocked)
+        } else if has(&[
+            "invalid puzzle date",
+            "not a valid advent of code year",
+            "not a valid advent of code day",
+        ]) {
+            Some(AocCommandError::InvalidDate)
+        } else if has(&["429 too many requests", "status: 429"]) {
+            Some(AocCommandError::Throttled)
+        } else if has(&[
+            "http request error",
+            "error sending request",
+            "dns error",
+            "connection refused",
+            "timed out",
+        ]) {
+            Some(AocCommandError::Network)
+        } else {
+            None
+        }
+    }
+
+    /// Suggestion for fixing the error.
+    fn hint(&self) -> Option<&'static str> {
+        match self {
+            AocCommandError::CommandNotFound => {
+                Some("run `cargo install aoc-cli --version 0.12.0` to install it.")
+            }
+            AocCommandError::MissingSession => Some(
+                "create the file ~/.adventofcode.session and paste your session cookie into it. See \"Configure aoc-cli integration\" in the readme.",
+            ),
+            AocCommandError::InvalidSession => Some(
+                "your session cookie may have expired. Copy a fresh `session` cookie from the Advent of Code website into ~/.adventofcode.session.",
+            ),
+            AocCommandError::InvalidDate => {
+                Some("check the day and the AOC_YEAR variable in .cargo/config.toml.")
+            }
+            AocCommandError::NotUnlocked => {
+                Some("puzzles unlock at midnight EST (UTC-5), try again once it is unlocked.")
+            }
+            AocCommandError::Throttled => Some("wait a few minutes before trying again."),
+            AocCommandError::Network => Some("check your internet connection and try again."),
+            AocCommandError::CommandNotCallable | AocCommandError::BadExitStatus(_) => None,
+        }
+    }
+}
+
 impl Display for AocCommandError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            AocCommandError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
-            AocCommandError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
+            AocCommandError::CommandNotFound => {
+                write!(f, "aoc-cli is not present in environment.")?
+            }
+            AocCommandError::CommandNotCallable => write!(f, "aoc-cli could not be called.")?,
+            AocCommandError::MissingSession => {
+                write!(f, "aoc-cli could not find a session cookie.")?
+            }
+            AocCommandError::InvalidSession => {
+                write!(f, "Advent of Code rejected the session cookie.")?;
+            }
+            AocCommandError::InvalidDate => write!(f, "the day or year is not valid.")?,
+            AocCommandError::NotUnlocked => write!(f, "the puzzle is not unlocked yet.")?,
+            AocCommandError::Throttled => write!(f, "Advent of Code throttled the request.")?,
+            AocCommandError::Network => write!(f, "Advent of Code could not be reached.")?,
             AocCommandError::BadExitStatus(_) => {
-                write!(f, "aoc-cli exited with a non-zero status.")
+                write!(f, "aoc-cli exited with a non-zero status.")?;
             }
         }
+
+        if let Some(hint) = self.hint() {
+            write!(f, "\nhint: {hint}")?;
+        }
+
+        Ok(())
     }
 }
 
+impl std::error::Error for AocCommandError {}
+
 pub fn check() -> Result<(), AocCommandError> {
     Command::new("aoc")
         .arg("-V")
@@ -49,25 +165,26 @@ pub fn read(day: Day) -> Result<Output, AocCommandError> {
     call_aoc_cli(&args)
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
+/// Download the puzzle description of a day, and its input if `input` is set.
+pub fn download(day: Day, input: bool) -> Result<Output, AocCommandError> {
     let input_path = get_input_path(day);
     let puzzle_path = get_puzzle_path(day);
 
-    let args = build_args(
-        "download",
-        &[
-            "--overwrite".into(),
-            "--input-file".into(),
-            input_path.to_string(),
-            "--puzzle-file".into(),
-            puzzle_path.to_string(),
-        ],
-        day,
-    );
+    let mut args = vec!["--overwrite".into()];
+
+    if input {
+        args.append(&mut vec!["--input-file".into(), input_path.to_string()]);
+    } else {
+        args.push("--puzzle-only".into());
+    }
+
+    args.append(&mut vec!["--puzzle-file".into(), puzzle_path.to_string()]);
 
-    let output = call_aoc_cli(&args)?;
+    let output = call_aoc_cli(&build_args("download", &args, day))?;
     println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    if input {
+        println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    }
     println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
     Ok(output)
 }
@@ -80,15 +197,15 @@ pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandErro
     call_aoc_cli(&args)
 }
 
-fn get_input_path(day: Day) -> String {
+pub(crate) fn get_input_path(day: Day) -> String {
     format!("data/inputs/{day}.txt")
 }
 
-fn get_puzzle_path(day: Day) -> String {
+pub fn get_puzzle_path(day: Day) -> String {
     format!("data/puzzles/{day}.md")
 }
 
-fn get_year() -> Option<u16> {
+pub(crate) fn get_year() -> Option<u16> {
     match std::env::var("AOC_YEAR") {
         Ok(x) => x.parse().ok().or(None),
         Err(_) => None,
@@ -108,18 +225,111 @@ fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     cmd_args
 }
 
+/// Call aoc-cli, echoing its output while capturing it so failures can be classified.
 fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
-    let output = Command::new("aoc")
+    let mut child = Command::new("aoc")
         .args(args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .output()
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => AocCommandError::CommandNotFound,
+            _ => AocCommandError::CommandNotCallable,
+        })?;
+
+    let stderr = child
+        .stderr
+        .take()
+        .map(|pipe| thread::spawn(move || echo(pipe, io::stderr())));
+
+    let stdout = child
+        .stdout
+        .take()
+        .map(|pipe| echo(pipe, io::stdout()))
+        .unwrap_or_default();
+
+    let stderr = stderr
+        .and_then(|handle| handle.join().ok())
+        .unwrap_or_default();
+
+    let status = child
+        .wait()
         .map_err(|_| AocCommandError::CommandNotCallable)?;
 
+    let output = Output {
+        status,
+        stdout,
+        stderr,
+    };
+
     if output.status.success() {
         Ok(output)
     } else {
-        Err(AocCommandError::BadExitStatus(output))
+        Err(AocCommandError::from_output(output))
+    }
+}
+
+/// Copy a stream line by line to `out`, returning everything that was read.
+fn echo(pipe: impl Read, mut out: impl Write) -> Vec<u8> {
+    let mut reader = BufReader::new(pipe);
+    let mut captured = vec![];
+    let mut line = vec![];
+
+    while reader.read_until(b'\n', &mut line).is_ok_and(|n| n > 0) {
+        let _ = out.write_all(&line);
+        let _ = out.flush();
+        captured.append(&mut line);
+    }
+
+    captured
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::AocCommandError;
+
+    #[test]
+    fn classifies_failures() {
+        let classify = |text: &str| AocCommandError::classify(text).map(|e| e.to_string());
+        let expected = |e: AocCommandError| Some(e.to_string());
+
+        assert_eq!(
+            classify("[ERROR aoc] 🔔 Session cookie file not found in home or config directory"),
+            expected(AocCommandError::MissingSession)
+        );
+        assert_eq!(
+            classify("[ERROR aoc] 🔔 Invalid session cookie"),
+            expected(AocCommandError::InvalidSession)
+        );
+        assert_eq!(
+            classify("[ERROR aoc] 🔔 Puzzle 25 of 2024 is still locked"),
+            expected(AocCommandError::NotUnlocked)
+        );
+        assert_eq!(
+            classify("[ERROR aoc] 🔔 2014 is not a valid Advent of Code year"),
+            expected(AocCommandError::InvalidDate)
+        );
+        assert_eq!(
+            classify("[ERROR aoc] 🔔 HTTP status client error (429 Too Many Requests) for url"),
+            expected(AocCommandError::Throttled)
+        );
+        assert_eq!(
+            classify("error: status: 429"),
+            expected(AocCommandError::Throttled)
+        );
+        assert_eq!(
+            classify("[ERROR aoc] 🔔 HTTP request error: error sending request for url"),
+            expected(AocCommandError::Network)
+        );
+        assert_eq!(classify("something else went wrong"), None);
+        assert_eq!(classify("Your puzzle answer was 4291."), None);
+        assert_eq!(classify("The elves timed out waiting."), None);
+        assert_eq!(
+            classify("[ERROR aoc] 🔔 Failed to parse answer 14290"),
+            None
+        );
     }
 }
@@ -0,0 +1,539 @@
+/// Built-in Advent of Code client, an alternative to the "aoc-cli" command-line.
+///
+/// Select it by setting `AOC_CLIENT=native` and building with the `native-client` feature.
+use std::{env, fmt::Display, fs, io, path::PathBuf};
+
+use regex::Regex;
+
+use crate::template::{
+    aoc_cli::{get_input_path, get_puzzle_path, get_year},
+    Day,
+};
+
+const CLIENT_ENV: &str = "AOC_CLIENT";
+const BASE_URL_ENV: &str = "AOC_BASE_URL";
+const SESSION_ENV: &str = "ADVENT_OF_CODE_SESSION";
+const SESSION_FILE_ENV: &str = "AOC_SESSION_FILE";
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// The template was built without the `native-client` feature.
+    NotEnabled,
+    /// No session cookie was found in the environment or the session file.
+    MissingSession,
+    /// `AOC_YEAR` is not set.
+    MissingYear,
+    /// The server rejected the session cookie.
+    NotLoggedIn,
+    /// The puzzle of this day is not unlocked yet.
+    NotUnlocked,
+    /// The server asked to slow down.
+    RateLimited,
+    /// The server responded with an unexpected status.
+    Http(u16),
+    /// The server could not be reached.
+    Network(String),
+    /// A downloaded file could not be written.
+    Write { path: String, source: io::Error },
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::NotEnabled => write!(
+                f,
+                "the native client is not enabled. Build with `--features native-client` or unset {CLIENT_ENV}."
+            ),
+            ClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set {SESSION_ENV} or store it in {}.",
+                session_file().display()
+            ),
+            ClientError::MissingYear => write!(f, "AOC_YEAR is not set."),
+            ClientError::NotLoggedIn => {
+                write!(f, "the session cookie was rejected, it may have expired.")
+            }
+            ClientError::NotUnlocked => write!(f, "the puzzle is not unlocked yet."),
+            ClientError::RateLimited => {
+                write!(f, "too many requests, wait a while before trying again.")
+            }
+            ClientError::Http(status) => write!(f, "unexpected response status {status}."),
+            ClientError::Network(e) => write!(f, "request failed: {e}"),
+            ClientError::Write { path, source } => write!(f, "could not write {path}: {source}"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+/// Whether `AOC_CLIENT=native` selects the built-in client over aoc-cli.
+pub fn is_selected() -> bool {
+    env::var(CLIENT_ENV).is_ok_and(|x| x == "native")
+}
+
+/// Client for the Advent of Code website.
+#[derive(Clone)]
+pub struct Client {
+    base_url: String,
+    #[cfg_attr(not(feature = "native-client"), allow(dead_code))]
+    session: String,
+    year: u16,
+}
+
+// NOTE: the session cookie grants access to the account, so it is never printed.
+impl std::fmt::Debug for Client {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Client")
+            .field("base_url", &self.base_url)
+            .field("session", &"<redacted>")
+            .field("year", &self.year)
+            .finish()
+    }
+}
+
+impl Client {
+    /// Configure a client from `AOC_BASE_URL`, `AOC_YEAR` and the session cookie.
+    pub fn from_env() -> Result<Self, ClientError> {
+        if !cfg!(feature = "native-client") {
+            return Err(ClientError::NotEnabled);
+        }
+
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+
+        Ok(Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: read_session().ok_or(ClientError::MissingSession)?,
+            year: get_year().ok_or(ClientError::MissingYear)?,
+        })
+    }
+
+    /// Download the puzzle description of a day to `data/`, and its input if `input` is set.
+    pub fn download(&self, day: Day, input: bool) -> Result<(), ClientError> {
+        let input_path = get_input_path(day);
+        self.download_to(
+            day,
+            input.then_some(input_path.as_str()),
+            &get_puzzle_path(day),
+        )
+    }
+
+    /// Download the puzzle description of a day to `puzzle_path`, and its input to `input_path` if set.
+    fn download_to(
+        &self,
+        day: Day,
+        input_path: Option<&str>,
+        puzzle_path: &str,
+    ) -> Result<(), ClientError> {
+        let puzzle = self.get(&self.day_url(day))?;
+
+        if let Some(input_path) = input_path {
+            let input = self.get(&format!("{}/input", self.day_url(day)))?;
+            write(input_path, &input)?;
+            println!("🎄 Successfully wrote input to \"{input_path}\".");
+        }
+
+        write(puzzle_path, &puzzle_to_markdown(&puzzle))?;
+        println!("🎄 Successfully wrote puzzle to \"{puzzle_path}\".");
+        Ok(())
+    }
+
+    /// Download the puzzle description of a day to `data/puzzles` and print it.
+    pub fn read(&self, day: Day) -> Result<(), ClientError> {
+        self.read_to(day, &get_puzzle_path(day))
+    }
+
+    /// Download the puzzle description of a day to `puzzle_path` and print it.
+    fn read_to(&self, day: Day, puzzle_path: &str) -> Result<(), ClientError> {
+        let puzzle = puzzle_to_markdown(&self.get(&self.day_url(day))?);
+        write(puzzle_path, &puzzle)?;
+        println!("{puzzle}");
+        Ok(())
+    }
+
+    /// Submit an answer and return the response as text.
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<String, ClientError> {
+        let response = self.post(
+            &format!("{}/answer", self.day_url(day)),
+            &[("level", &part.to_string()), ("answer", answer)],
+        )?;
+        Ok(puzzle_to_markdown(&response))
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+}
+
+#[cfg(feature = "native-client")]
+impl Client {
+    fn agent() -> ureq::Agent {
+        ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(Some(std::time::Duration::from_secs(30)))
+            .user_agent("github.com/fspoettel/advent-of-code-rust")
+            .build()
+            .into()
+    }
+
+    fn get(&self, url: &str) -> Result<String, ClientError> {
+        let response = Self::agent()
+            .get(url)
+            .header("Cookie", &format!("session={}", self.session))
+            .call();
+        read_response(response)
+    }
+
+    fn post(&self, url: &str, form: &[(&str, &str)]) -> Result<String, ClientError> {
+        let response = Self::agent()
+            .post(url)
+            .header("Cookie", &format!("session={}", self.session))
+            .send_form(form.iter().copied());
+        read_response(response)
+    }
+}
+
+#[cfg(feature = "native-client")]
+fn read_response(
+    response: Result<ureq::http::Response<ureq::Body>, ureq::Error>,
+) -> Result<String, ClientError> {
+    let mut response = response.map_err(|e| ClientError::Network(e.to_string()))?;
+    let body = response
+        .body_mut()
+        .read_to_string()
+        .map_err(|e| ClientError::Network(e.to_string()))?;
+    check_status(response.status().as_u16(), &body)?;
+    Ok(body)
+}
+
+#[cfg(not(feature = "native-client"))]
+impl Client {
+    fn get(&self, _url: &str) -> Result<String, ClientError> {
+        Err(ClientError::NotEnabled)
+    }
+
+    fn post(&self, _url: &str, _form: &[(&str, &str)]) -> Result<String, ClientError> {
+        Err(ClientError::NotEnabled)
+    }
+}
+
+/// Map a response status to an error.
+#[cfg_attr(not(feature = "native-client"), allow(dead_code))]
+fn check_status(status: u16, body: &str) -> Result<(), ClientError> {
+    match status {
+        200..=299 => Ok(()),
+        400 | 401 | 403 => Err(ClientError::NotLoggedIn),
+        404 => Err(ClientError::NotUnlocked),
+        429 => Err(ClientError::RateLimited),
+        // NOTE: the server responds with an internal error to malformed session cookies.
+        500 if body.contains("log in") => Err(ClientError::NotLoggedIn),
+        _ => Err(ClientError::Http(status)),
+    }
+}
+
+/// Path of the session file, the same one that aoc-cli reads.
+fn session_file() -> PathBuf {
+    env::var(SESSION_FILE_ENV).map_or_else(
+        |_| PathBuf::from(env::var("HOME").unwrap_or_default()).join(".adventofcode.session"),
+        PathBuf::from,
+    )
+}
+
+fn read_session() -> Option<String> {
+    env::var(SESSION_ENV)
+        .ok()
+        .or_else(|| fs::read_to_string(session_file()).ok())
+        .map(|x| x.trim().to_string())
+        .filter(|x| !x.is_empty())
+}
+
+fn write(path: &str, content: &str) -> Result<(), ClientError> {
+    fs::write(path, content).map_err(|source| ClientError::Write {
+        path: path.into(),
+        source,
+    })
+}
+
+/// Convert a puzzle (or submission) page to markdown like aoc-cli does.
+///
+/// Only the articles and the "Your puzzle answer was" lines are kept.
+fn puzzle_to_markdown(html: &str) -> String {
+    let sections =
+        Regex::new(r"(?s)<article[^>]*>.*?</article>|<p>Your puzzle answer was.*?</p>").unwrap();
+    let blank_lines = Regex::new(r"\n{3,}").unwrap();
+
+    let markdown: String = sections
+        .find_iter(html)
+        .map(|m| html_to_markdown(m.as_str()))
+        .collect();
+
+    blank_lines
+        .replace_all(markdown.trim(), "\n\n")
+        .into_owned()
+        + "\n"
+}
+
+/// Convert the small subset of html used in puzzle descriptions to markdown.
+fn html_to_markdown(html: &str) -> String {
+    let tags = Regex::new(r"<(/?)(\w+)[^>]*>").unwrap();
+
+    let mut markdown = String::new();
+    let mut in_pre = false;
+    let mut last = 0;
+
+    for c in tags.captures_iter(html) {
+        let tag = c.get(0).unwrap();
+        markdown.push_str(&decode_entities(&html[last..tag.start()]));
+        last = tag.end();
+
+        let is_close = !c[1].is_empty();
+
+        match (&c[2], is_close) {
+            ("pre", false) => {
+                markdown.push_str("\n```\n");
+                in_pre = true;
+            }
+            ("pre", true) => {
+                if !markdown.ends_with('\n') {
+                    markdown.push('\n');
+                }
+                markdown.push_str("```\n\n");
+                in_pre = false;
+            }
+            ("code", _) if !in_pre => markdown.push('`'),
+            ("em", _) if !in_pre => markdown.push('*'),
+            ("h2", false) => markdown.push('\n'),
+            ("h2", true) => markdown.push_str("\n----------\n\n"),
+            ("p" | "ul", true) => markdown.push_str("\n\n"),
+            ("li", false) => markdown.push_str("* "),
+            ("li", true) | ("br", _) => markdown.push('\n'),
+            _ => {}
+        }
+    }
+
+    markdown.push_str(&decode_entities(&html[last..]));
+    markdown
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check_status, puzzle_to_markdown, Client, ClientError};
+    use crate::template::puzzle::Puzzle;
+
+    const PAGE: &str = r#"<!DOCTYPE html><html><body><header>Advent of Code</header><main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>Throughout the Chief's office:</p>
+<p>For example:</p>
+<pre><code>3   4
+4   3
+2   5
+</code></pre>
+<p>Add up the distances: a <em>total distance</em> of <code><em>11</em></code>!</p>
+<ul>
+<li>Compare <code>a &lt; b</code> &amp; more.</li>
+</ul>
+</article>
+<p>Your puzzle answer was <code>1110981</code>.</p><article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>The similarity score is <code><em>31</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>24869388</code>.</p>
+<p class="day-success">Both parts of this puzzle are complete!</p>
+</main></body></html>"#;
+
+    #[test]
+    fn converts_puzzle_to_markdown() {
+        let markdown = puzzle_to_markdown(PAGE);
+        assert!(markdown.starts_with("--- Day 1: Historian Hysteria ---\n----------\n\n"));
+        assert!(markdown.contains("For example:\n\n```\n3   4\n4   3\n2   5\n```\n"));
+        assert!(markdown.contains("a *total distance* of `*11*`!"));
+        assert!(markdown.contains("* Compare `a < b` & more.\n"));
+        assert!(!markdown.contains("Both parts"));
+
+        let puzzle = Puzzle::parse(&markdown);
+        assert_eq!(puzzle.blocks.len(), 1);
+        assert_eq!(puzzle.suggested_example(), Some(0));
+        assert_eq!(
+            puzzle.example_answers,
+            [Some("11".to_string()), Some("31".to_string())]
+        );
+        assert_eq!(
+            puzzle.puzzle_answers,
+            [Some("1110981".to_string()), Some("24869388".to_string())]
+        );
+    }
+
+    #[test]
+    fn converts_submission_response() {
+        let page = "<main><article><p>That's not the right answer; your answer is too high. <a href=\"/2024/day/1\">[Return to Day 1]</a></p></article></main>";
+        assert_eq!(
+            puzzle_to_markdown(page),
+            "That's not the right answer; your answer is too high. [Return to Day 1]\n"
+        );
+    }
+
+    #[test]
+    fn classifies_status() {
+        assert!(check_status(200, "").is_ok());
+        assert!(matches!(
+            check_status(400, "Puzzle inputs differ by user.  Please log in"),
+            Err(ClientError::NotLoggedIn)
+        ));
+        assert!(matches!(
+            check_status(404, ""),
+            Err(ClientError::NotUnlocked)
+        ));
+        assert!(matches!(
+            check_status(429, ""),
+            Err(ClientError::RateLimited)
+        ));
+        assert!(matches!(check_status(502, ""), Err(ClientError::Http(502))));
+    }
+
+    fn client(base_url: &str, session: &str) -> Client {
+        Client {
+            base_url: base_url.into(),
+            session: session.into(),
+            year: 2024,
+        }
+    }
+
+    #[test]
+    fn redacts_session() {
+        let debug = format!("{:?}", client("http://localhost", "53616c7465645f5f"));
+        assert!(debug.contains("http://localhost"));
+        assert!(!debug.contains("53616c7465645f5f"));
+    }
+
+    /// Serve a fake Advent of Code on a local port. Day 1 is unlocked, day 2 is locked, and requests for
+    /// day 3 are rate limited. Every request without the `secret` session is rejected.
+    #[cfg(feature = "native-client")]
+    fn serve() -> String {
+        use std::{
+            io::{BufRead, BufReader, Read, Write},
+            net::TcpListener,
+            thread,
+        };
+
+        const PUZZLE: &str =
+            "<main><article><h2>--- Day 1: Test ---</h2><p>Count <em>everything</em>.</p></article></main>";
+        const LOGGED_OUT: &str =
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.";
+        const LOCKED: &str = "Please don't repeatedly request this endpoint before it unlocks!";
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        thread::spawn(move || {
+            for mut stream in listener.incoming().map_while(Result::ok) {
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+
+                let mut session = None;
+                let mut content_length = 0;
+
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let line = line.trim_end();
+
+                    if line.is_empty() {
+                        break;
+                    }
+
+                    let (name, value) = line.split_once(": ").unwrap_or((line, ""));
+
+                    match name.to_ascii_lowercase().as_str() {
+                        "cookie" => session = value.strip_prefix("session=").map(String::from),
+                        "content-length" => content_length = value.parse().unwrap(),
+                        _ => {}
+                    }
+                }
+
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                let body = String::from_utf8(body).unwrap();
+
+                let (status, page) = match (request_line.trim_end(), session.as_deref()) {
+                    (_, session) if session != Some("secret") => (400, LOGGED_OUT.into()),
+                    ("GET /2024/day/1 HTTP/1.1", _) => (200, PUZZLE.into()),
+                    ("GET /2024/day/1/input HTTP/1.1", _) => (200, "1\n2\n3\n".into()),
+                    ("POST /2024/day/1/answer HTTP/1.1", _) => (
+                        200,
+                        format!("<main><article><p>Received {body}.</p></article></main>"),
+                    ),
+                    (line, _) if line.contains("/2024/day/3") => (429, String::new()),
+                    _ => (404, LOCKED.into()),
+                };
+
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {status} Test\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{page}",
+                    page.len()
+                );
+            }
+        });
+
+        base_url
+    }
+
+    #[test]
+    #[cfg(feature = "native-client")]
+    fn talks_to_server() {
+        use crate::day;
+        use std::{env, fs, process};
+
+        let base_url = serve();
+        let expired = client(&base_url, "expired");
+        let client = client(&base_url, "secret");
+
+        let dir = env::temp_dir().join(format!("aoc-client-test-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let input_path = dir.join("01.txt").to_string_lossy().into_owned();
+        let puzzle_path = dir.join("01.md").to_string_lossy().into_owned();
+
+        client
+            .download_to(day!(1), Some(&input_path), &puzzle_path)
+            .unwrap();
+        assert_eq!(fs::read_to_string(&input_path).unwrap(), "1\n2\n3\n");
+        assert_eq!(
+            fs::read_to_string(&puzzle_path).unwrap(),
+            "--- Day 1: Test ---\n----------\n\nCount *everything*.\n"
+        );
+
+        fs::remove_file(&puzzle_path).unwrap();
+        client.read_to(day!(1), &puzzle_path).unwrap();
+        assert!(fs::read_to_string(&puzzle_path)
+            .unwrap()
+            .contains("Count *everything*."));
+
+        assert_eq!(
+            client.submit(day!(1), 2, "42").unwrap(),
+            "Received level=2&answer=42.\n"
+        );
+
+        assert!(matches!(
+            expired.read_to(day!(1), &puzzle_path),
+            Err(ClientError::NotLoggedIn)
+        ));
+        assert!(matches!(
+            client.download_to(day!(2), None, &puzzle_path),
+            Err(ClientError::NotUnlocked)
+        ));
+        assert!(matches!(
+            client.submit(day!(3), 1, "42"),
+            Err(ClientError::RateLimited)
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
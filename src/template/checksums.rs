@@ -0,0 +1,150 @@
+/// Checksums of downloaded puzzle inputs, used to tell intact inputs from edited or truncated ones.
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{timings::to_sorted_string, Day};
+
+static CHECKSUMS_FILE_PATH: &str = "./data/inputs/checksums.json";
+
+/// Result of comparing an input against its recorded checksum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verification {
+    /// The input matches the checksum recorded when it was downloaded.
+    Verified,
+    /// The input changed since it was downloaded.
+    Mismatch,
+    /// No checksum was recorded for this input.
+    Unknown,
+}
+
+/// FNV-1a hash of `content` as hex. Stable across platforms and Rust versions, unlike `DefaultHasher`.
+pub fn checksum(content: &[u8]) -> String {
+    let hash = content
+        .iter()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("{hash:016x}")
+}
+
+/// Checksums of downloaded inputs, keyed by day.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Checksums {
+    pub data: BTreeMap<Day, String>,
+}
+
+impl Checksums {
+    /// Dehydrate checksums to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        fs::write(CHECKSUMS_FILE_PATH, to_sorted_string(&JsonValue::from(self)))
+    }
+
+    /// Rehydrate checksums from a JSON file. If not present, returns no checksums.
+    /// Fails if the file exists but can not be read or parsed, so that it is never overwritten.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(CHECKSUMS_FILE_PATH) {
+            Ok(json) => {
+                Checksums::try_from(json).map_err(|e| format!("invalid {CHECKSUMS_FILE_PATH}: {e}"))
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("could not read {CHECKSUMS_FILE_PATH}: {e}.")),
+        }
+    }
+
+    /// Record the checksum of a freshly downloaded input.
+    pub fn set(&mut self, day: Day, content: &[u8]) {
+        self.data.insert(day, checksum(content));
+    }
+
+    /// Compare an input against the checksum recorded for its day.
+    pub fn verify(&self, day: Day, content: &[u8]) -> Verification {
+        match self.data.get(&day) {
+            None => Verification::Unknown,
+            Some(expected) if *expected == checksum(content) => Verification::Verified,
+            Some(_) => Verification::Mismatch,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Checksums> for JsonValue {
+    fn from(value: &Checksums) -> Self {
+        let map: HashMap<String, JsonValue> = value
+            .data
+            .iter()
+            .map(|(day, checksum)| (day.to_string(), JsonValue::String(checksum.clone())))
+            .collect();
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Checksums {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_days = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let mut data = BTreeMap::new();
+
+        for (key, value) in json_days {
+            let day = Day::from_str(key).map_err(|_| format!("`{key}` is not a valid day."))?;
+            let checksum = value
+                .get::<String>()
+                .ok_or(format!("expected checksum of day {day} to be a string."))?;
+            data.insert(day, checksum.clone());
+        }
+
+        Ok(Checksums { data })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{checksum, Checksums, Verification};
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn computes_stable_checksums() {
+        assert_eq!(checksum(b""), "cbf29ce484222325");
+        assert_eq!(checksum(b"a"), "af63dc4c8601ec8c");
+        assert_ne!(checksum(b"3   4\n"), checksum(b"3   4"));
+    }
+
+    #[test]
+    fn verifies_inputs() {
+        let mut checksums = Checksums::default();
+        checksums.set(day!(1), b"3   4\n");
+
+        assert_eq!(
+            checksums.verify(day!(1), b"3   4\n"),
+            Verification::Verified
+        );
+        assert_eq!(checksums.verify(day!(1), b"3   4"), Verification::Mismatch);
+        assert_eq!(checksums.verify(day!(2), b"1"), Verification::Unknown);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let mut checksums = Checksums::default();
+        checksums.set(day!(1), b"1");
+        checksums.set(day!(12), b"2");
+
+        let json = JsonValue::from(&checksums).stringify().unwrap();
+        assert_eq!(Checksums::try_from(json).unwrap(), checksums);
+    }
+}
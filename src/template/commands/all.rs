@@ -1,5 +1,82 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::time::Duration;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{
+    all_days,
+    run_multi::{run_multi, RunOptions},
+};
+
+pub fn handle(is_release: bool, is_dhat: bool, in_process: bool, timeout: Option<Duration>) {
+    if in_process && is_dhat {
+        eprintln!("--in-process can not be combined with --dhat.");
+        std::process::exit(1);
+    }
+
+    if in_process {
+        run_in_process(is_release, timeout);
+        return;
+    }
+
+    let options = RunOptions {
+        is_release,
+        is_dhat,
+        count_alloc: false,
+        bench: None,
+        timeout,
+    };
+
+    let run = run_multi(&all_days().collect(), &options);
+    run.print_summary();
+    run.exit_on_failures();
+}
+
+/// Run all solutions from this binary via the solution registry.
+#[cfg(feature = "registry")]
+fn run_in_process(is_release: bool, timeout: Option<Duration>) {
+    use crate::template::registry;
+
+    // the profile is fixed at compile time, so switch to a build of the requested one.
+    if is_release == cfg!(debug_assertions) {
+        rerun_in_process(is_release, timeout);
+        return;
+    }
+
+    let run = registry::run_days(&all_days().collect(), timeout, None);
+    run.print_summary();
+    run.exit_on_failures();
+}
+
+/// The registry is only compiled with the `registry` feature, so re-run this command with it enabled.
+#[cfg(not(feature = "registry"))]
+fn run_in_process(is_release: bool, timeout: Option<Duration>) {
+    rerun_in_process(is_release, timeout);
+}
+
+/// Re-run `all --in-process` through cargo, with the `registry` feature and the requested profile.
+fn rerun_in_process(is_release: bool, timeout: Option<Duration>) {
+    use std::process::{self, Command};
+
+    let mut args: Vec<String> = ["run", "--quiet", "--features", "registry"]
+        .map(Into::into)
+        .into();
+
+    if is_release {
+        args.push("--release".into());
+    }
+
+    args.extend(["--", "all", "--in-process"].map(Into::into));
+
+    // the first `--release` selects the build profile, this one tells the rebuilt command which profile was requested.
+    if is_release {
+        args.push("--release".into());
+    }
+
+    if let Some(timeout) = timeout {
+        args.extend(["--timeout".into(), timeout.as_secs_f64().to_string()]);
+    }
+
+    let status = Command::new("cargo").args(&args).status().unwrap();
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 }
@@ -1,14 +1,183 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::{
+    all_days,
+    answers::Answers,
+    aoc_cli::{self, get_input_path, AocCommandError},
+    aoc_client::{self, Client, ClientError},
+    checksums::{Checksums, Verification},
+    puzzle::Puzzle,
+    Day,
+};
+use std::{fmt::Display, fs, process, thread, time::Duration};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+/// Download the puzzle description of a day, and its input unless a non-empty input exists and
+/// `overwrite` is not set.
+pub fn handle(day: Day, overwrite: bool) {
+    let input = overwrite || !has_input(day);
+
+    if !input {
+        println!(
+            "Kept existing input \"{}\". Pass --overwrite to download it again.",
+            get_input_path(day)
+        );
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if let Err(e) = fetch(day, input) {
+        eprintln!("{e}");
         process::exit(1);
+    }
+
+    harvest_answers(day);
+}
+
+/// Download the inputs of all days that do not have one yet, waiting `delay` between downloads.
+///
+/// Inputs that changed since they were downloaded, or have no recorded checksum, are only replaced
+/// if `overwrite` is set.
+pub fn handle_missing(overwrite: bool, delay: Duration) {
+    let checksums = match Checksums::read_from_file() {
+        Ok(checksums) => checksums,
+        Err(e) => {
+            eprintln!("Failed to verify inputs: {e}");
+            process::exit(1);
+        }
+    };
+    let mut downloaded = 0;
+
+    for day in all_days() {
+        let path = get_input_path(day);
+
+        if let Some(content) = fs::read(&path).ok().filter(|x| !x.is_empty()) {
+            match checksums.verify(day, &content) {
+                Verification::Verified => {
+                    println!("Day {day}: input present, checksum verified.");
+                    continue;
+                }
+                Verification::Unknown if !overwrite => {
+                    println!("Day {day}: input present, no checksum recorded. Pass --overwrite to download it again.");
+                    continue;
+                }
+                Verification::Mismatch if !overwrite => {
+                    println!("Day {day}: input changed since it was downloaded, kept it. Pass --overwrite to download it again.");
+                    continue;
+                }
+                Verification::Unknown | Verification::Mismatch => {}
+            }
+        }
+
+        if downloaded > 0 {
+            thread::sleep(delay);
+        }
+
+        println!("Day {day}: downloading...");
+
+        match fetch(day, true) {
+            Ok(()) => {
+                harvest_answers(day);
+                downloaded += 1;
+            }
+            Err(e) if e.is_locked => {
+                println!("Day {day} is not unlocked yet, stopping.");
+                break;
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    println!("🎄 Downloaded {downloaded} missing input(s).");
+}
+
+fn has_input(day: Day) -> bool {
+    fs::metadata(get_input_path(day)).is_ok_and(|x| x.len() > 0)
+}
+
+struct FetchError {
+    message: String,
+    /// The puzzle of the day is not unlocked yet.
+    is_locked: bool,
+}
+
+impl Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Download a day with the selected client and record the checksum of a downloaded input.
+fn fetch(day: Day, input: bool) -> Result<(), FetchError> {
+    if aoc_client::is_selected() {
+        Client::from_env()
+            .and_then(|client| client.download(day, input))
+            .map_err(|e| FetchError {
+                is_locked: matches!(e, ClientError::NotUnlocked),
+                message: format!("failed to download day {day}: {e}"),
+            })?;
+    } else {
+        if aoc_cli::check().is_err() {
+            return Err(FetchError {
+                message: "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.".into(),
+                is_locked: false,
+            });
+        }
+
+        aoc_cli::download(day, input).map_err(|e| FetchError {
+            is_locked: matches!(e, AocCommandError::NotUnlocked),
+            message: format!("failed to call aoc-cli: {e}"),
+        })?;
+    }
+
+    if input {
+        record_checksum(day);
+    }
+
+    Ok(())
+}
+
+fn record_checksum(day: Day) {
+    let Ok(content) = fs::read(get_input_path(day)) else {
+        return;
+    };
+
+    let result = Checksums::read_from_file().and_then(|mut checksums| {
+        checksums.set(day, &content);
+        checksums.store_file().map_err(|e| e.to_string())
+    });
+
+    if let Err(e) = result {
+        eprintln!("Failed to store input checksum: {e}");
+    }
+}
+
+/// Store the answers that the downloaded puzzle description confirms as correct in `data/answers.json`.
+pub(crate) fn harvest_answers(day: Day) {
+    let Ok(puzzle) = Puzzle::read_from_file(day) else {
+        return;
     };
+
+    let mut answers = match Answers::read_from_file() {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Failed to store confirmed answers: {e}");
+            return;
+        }
+    };
+    let changed = answers.merge_confirmed(day, &puzzle.puzzle_answers);
+
+    if changed.is_empty() {
+        return;
+    }
+
+    if let Err(e) = answers.store_file() {
+        eprintln!("Failed to store confirmed answers: {e}");
+        return;
+    }
+
+    for part in changed {
+        println!(
+            "🎄 Stored confirmed answer for part {part}: {}",
+            answers.get(day, None, part).unwrap_or_default()
+        );
+    }
 }
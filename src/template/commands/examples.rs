@@ -0,0 +1,195 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+    process,
+};
+
+use crate::template::{
+    aoc_cli::get_puzzle_path,
+    examples::{answers_path, ExampleAnswers},
+    puzzle::Puzzle,
+    Day,
+};
+
+/// Which code blocks of the puzzle description to write to `data/examples`.
+#[derive(Clone, Debug, Default)]
+pub struct BlockSelection {
+    /// Block for the shared example `<day>.txt`. Suggested from the description if not set.
+    pub shared: Option<usize>,
+    /// Blocks for the part-specific examples `<day>-1.txt` and `<day>-2.txt`.
+    pub parts: [Option<usize>; 2],
+    /// Prompt for the shared example block instead of using the suggestion.
+    pub interactive: bool,
+}
+
+pub fn handle(day: Day, list: bool, selection: &BlockSelection, overwrite: bool) {
+    let puzzle = match Puzzle::read_from_file(day) {
+        Ok(puzzle) => puzzle,
+        Err(e) => {
+            eprintln!(
+                "Could not read puzzle description \"{}\": {e}. Run `cargo download {day}` to download it.",
+                get_puzzle_path(day)
+            );
+            process::exit(1);
+        }
+    };
+
+    if puzzle.blocks.is_empty() {
+        eprintln!("The puzzle description of day {day} contains no code blocks.");
+        process::exit(1);
+    }
+
+    let suggested = puzzle.suggested_example();
+
+    if list {
+        print_blocks(&puzzle, suggested);
+        return;
+    }
+
+    let shared = if selection.interactive {
+        print_blocks(&puzzle, suggested);
+        prompt_block(suggested)
+    } else {
+        selection.shared.or(suggested)
+    };
+
+    let mut files: Vec<(String, usize)> = vec![];
+
+    if let Some(index) = shared {
+        files.push((day.to_string(), index));
+    }
+
+    for (i, index) in selection.parts.iter().enumerate() {
+        if let Some(index) = index {
+            files.push((format!("{day}-{}", i + 1), *index));
+        }
+    }
+
+    for (name, index) in &files {
+        let Some(block) = puzzle.blocks.get(*index) else {
+            eprintln!(
+                "Block {index} does not exist, the description has {} code blocks.",
+                puzzle.blocks.len()
+            );
+            process::exit(1);
+        };
+
+        write_example(name, &block.content, overwrite);
+    }
+
+    store_answers(day, &puzzle, selection, overwrite);
+}
+
+fn print_blocks(puzzle: &Puzzle, suggested: Option<usize>) {
+    for (i, block) in puzzle.blocks.iter().enumerate() {
+        let lines: Vec<&str> = block.content.lines().collect();
+        let marker = if Some(i) == suggested {
+            " (suggested example)"
+        } else {
+            ""
+        };
+
+        println!("[{i}] part {}, {} lines{marker}", block.part, lines.len());
+        for line in lines.iter().take(3) {
+            println!("    {line}");
+        }
+        if lines.len() > 3 {
+            println!("    ...");
+        }
+    }
+}
+
+fn prompt_block(suggested: Option<usize>) -> Option<usize> {
+    let default = suggested.map_or(String::new(), |i| format!(" [{i}]"));
+    print!("Example block{default}: ");
+    let _ = io::stdout().flush();
+
+    let mut line = String::new();
+    if io::stdin().read_line(&mut line).is_err() {
+        return suggested;
+    }
+
+    match line.trim() {
+        "" => suggested,
+        x => match x.parse() {
+            Ok(index) => Some(index),
+            Err(_) => {
+                eprintln!("`{x}` is not a block index.");
+                process::exit(1);
+            }
+        },
+    }
+}
+
+/// Write an example file, unless it already has content and `overwrite` is not set.
+fn write_example(name: &str, content: &str, overwrite: bool) {
+    let path = format!("data/examples/{name}.txt");
+
+    let has_content = fs::read_to_string(&path).is_ok_and(|x| !x.trim().is_empty());
+    if has_content && !overwrite {
+        println!("Kept existing example \"{path}\". Pass --overwrite to replace it.");
+        return;
+    }
+
+    match fs::write(&path, content) {
+        Ok(()) => println!("Wrote example \"{path}\"."),
+        Err(e) => {
+            eprintln!("Failed to write example \"{path}\": {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Declare the guessed example answers, keeping answers that were already declared unless `overwrite` is set.
+fn store_answers(day: Day, puzzle: &Puzzle, selection: &BlockSelection, overwrite: bool) {
+    let mut answers = if Path::new(&answers_path(day)).exists() {
+        match ExampleAnswers::read_from_file(day) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        }
+    } else {
+        ExampleAnswers::default()
+    };
+
+    let mut changed = false;
+
+    for part in [1, 2] {
+        let Some(answer) = &puzzle.example_answers[usize::from(part - 1)] else {
+            continue;
+        };
+
+        // answers of a part belong to its part-specific example, if one was picked.
+        let name = if selection.parts[usize::from(part - 1)].is_some() {
+            format!("{day}-{part}")
+        } else {
+            day.to_string()
+        };
+
+        if matches!(answers.get(&name, part), Some(Some(_))) && !overwrite {
+            continue;
+        }
+
+        answers.set(&name, part, Some(answer));
+        println!("Guessed the answer of part {part} for example {name}: {answer}");
+        changed = true;
+    }
+
+    if !changed {
+        return;
+    }
+
+    match answers.store_file(day) {
+        Ok(()) => println!(
+            "Updated \"{}\". Check the guessed answers against the puzzle description.",
+            answers_path(day)
+        ),
+        Err(e) => {
+            eprintln!("Failed to store example answers: {e}");
+            process::exit(1);
+        }
+    }
+}
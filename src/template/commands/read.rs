@@ -1,8 +1,22 @@
 use std::process;
 
-use crate::template::{aoc_cli, Day};
+use crate::template::{
+    aoc_cli,
+    aoc_client::{self, Client},
+    Day,
+};
 
 pub fn handle(day: Day) {
+    if aoc_client::is_selected() {
+        if let Err(e) = Client::from_env().and_then(|client| client.read(day)) {
+            eprintln!("failed to read day {day}: {e}");
+            process::exit(1);
+        }
+
+        super::download::harvest_answers(day);
+        return;
+    }
+
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
@@ -12,4 +26,6 @@ pub fn handle(day: Day) {
         eprintln!("failed to call aoc-cli: {e}");
         process::exit(1);
     };
+
+    super::download::harvest_answers(day);
 }
@@ -1,79 +1,156 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{
+    examples::{self, ExampleAnswers},
+    Day,
+};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
-    let mut file = OpenOptions::new();
-    if overwrite {
-        file.create(true);
-    } else {
-        file.create_new(true);
-    }
-    file.truncate(true).write(true).open(path)
+/// What scaffolding does with one file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Action {
+    Create,
+    Keep,
+    /// Replace the file after copying it to `backup`.
+    Replace {
+        backup: String,
+    },
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// A file created by scaffolding.
+struct Artifact {
+    label: &'static str,
+    path: String,
+    contents: String,
+    /// Whether `--overwrite` may replace an existing file. Data files are never replaced.
+    replaceable: bool,
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
-
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
-    };
-
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+impl Artifact {
+    fn plan(&self, overwrite: bool) -> Action {
+        if !Path::new(&self.path).exists() {
+            return Action::Create;
         }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+
+        let is_unchanged = fs::read_to_string(&self.path).is_ok_and(|x| x == self.contents);
+
+        if overwrite && self.replaceable && !is_unchanged {
+            Action::Replace {
+                backup: backup_path(&self.path),
+            }
+        } else {
+            Action::Keep
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+    fn apply(&self, action: &Action) -> Result<(), io::Error> {
+        match action {
+            Action::Create => OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&self.path)?
+                .write_all(self.contents.as_bytes()),
+            Action::Keep => Ok(()),
+            Action::Replace { backup } => {
+                fs::copy(&self.path, backup)?;
+                fs::write(&self.path, &self.contents)
+            }
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    fn describe(&self, action: &Action, dry_run: bool) -> String {
+        let (path, label) = (&self.path, self.label);
+
+        match (action, dry_run) {
+            (Action::Create, false) => format!("Created {label} \"{path}\""),
+            (Action::Create, true) => format!("Would create {label} \"{path}\""),
+            (Action::Keep, false) => format!("Kept existing {label} \"{path}\""),
+            (Action::Keep, true) => format!("Would keep existing {label} \"{path}\""),
+            (Action::Replace { backup }, false) => {
+                format!("Replaced {label} \"{path}\", backed up to \"{backup}\"")
+            }
+            (Action::Replace { backup }, true) => {
+                format!("Would replace {label} \"{path}\", backing up to \"{backup}\"")
+            }
         }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    }
+}
+
+/// First of `<path>.bak`, `<path>.bak.1`, ... that does not exist yet.
+fn backup_path(path: &str) -> String {
+    let mut backup = format!("{path}.bak");
+    let mut i = 1;
+
+    while Path::new(&backup).exists() {
+        backup = format!("{path}.bak.{i}");
+        i += 1;
+    }
+
+    backup
+}
+
+/// Scaffold the files of a day. Existing data files are always kept, `overwrite` only resets the
+/// module after backing it up. With `dry_run`, only the plan is printed.
+pub fn handle(day: Day, overwrite: bool, dry_run: bool) {
+    let mut answers = ExampleAnswers::default();
+    answers.set(&day.to_string(), 1, None);
+    answers.set(&day.to_string(), 2, None);
+
+    let artifacts = [
+        Artifact {
+            label: "module file",
+            path: format!("src/bin/{day}.rs"),
+            contents: MODULE_TEMPLATE.replace("%DAY_NUMBER%", &day.into_inner().to_string()),
+            replaceable: true,
+        },
+        Artifact {
+            label: "input file",
+            path: format!("data/inputs/{day}.txt"),
+            contents: String::new(),
+            replaceable: false,
+        },
+        Artifact {
+            label: "example file",
+            path: format!("data/examples/{day}.txt"),
+            contents: String::new(),
+            replaceable: false,
+        },
+        Artifact {
+            label: "example answers file",
+            path: examples::answers_path(day),
+            contents: answers.to_json(),
+            replaceable: false,
+        },
+    ];
+
+    for artifact in &artifacts {
+        let action = artifact.plan(overwrite);
+
+        if !dry_run {
+            if let Err(e) = artifact.apply(&action) {
+                eprintln!(
+                    "Failed to write {} \"{}\": {e}",
+                    artifact.label, artifact.path
+                );
+                process::exit(1);
+            }
         }
+
+        println!("{}", artifact.describe(&action, dry_run));
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+
+    if dry_run {
+        println!("Dry run, no files were changed.");
+    } else {
+        println!("🎄 Type `cargo solve {day}` to run your solution.");
+    }
 }
@@ -1,8 +1,84 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, ExitStatus, Stdio};
 
-use crate::template::Day;
+use crate::template::{
+    input::named_inputs, runner::BenchOptions, try_read_file, Day, InputSource, ANSI_BOLD,
+    ANSI_RESET,
+};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    record_part: Option<u8>,
+    bench: Option<&BenchOptions>,
+    input: &InputSource,
+) {
+    let mut cmd_args = build_args(day, release, dhat, bench, input);
+
+    if let Some(submit_part) = submit_part {
+        cmd_args.push("--submit".to_string());
+        cmd_args.push(submit_part.to_string());
+    }
+
+    if let Some(record_part) = record_part {
+        cmd_args.push("--record".to_string());
+        cmd_args.push(record_part.to_string());
+    }
+
+    let status = run(&cmd_args);
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
+
+/// Run the solution against the puzzle input and every named input in `data/inputs/<day>/`.
+/// Exits with a non-zero status if the solution fails or gives a wrong answer for any of them.
+pub fn handle_all_inputs(day: Day, release: bool, dhat: bool, bench: Option<&BenchOptions>) {
+    let mut inputs: Vec<InputSource> = named_inputs(day)
+        .into_iter()
+        .map(InputSource::Named)
+        .collect();
+
+    if try_read_file("inputs", day).is_ok() || inputs.is_empty() {
+        inputs.insert(0, InputSource::Puzzle);
+    }
+
+    let mut failed: Vec<String> = vec![];
+
+    for (i, input) in inputs.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        let name = input.name().unwrap_or("puzzle input");
+        println!("{ANSI_BOLD}Input: {name}{ANSI_RESET}");
+        println!("------");
+
+        let status = run(&build_args(day, release, dhat, bench, input));
+
+        if !status.success() {
+            failed.push(name.to_string());
+        }
+    }
+
+    if !failed.is_empty() {
+        eprintln!();
+        for name in failed {
+            eprintln!("✗ Day {day} failed or gave a wrong answer for input {name}.");
+        }
+        process::exit(1);
+    }
+}
+
+fn build_args(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    bench: Option<&BenchOptions>,
+    input: &InputSource,
+) -> Vec<String> {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -18,17 +94,22 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
+    if let Some(bench) = bench {
+        cmd_args.push("--time".to_string());
+        cmd_args.extend(bench.to_args());
     }
 
+    cmd_args.extend(input.to_args());
+    cmd_args
+}
+
+fn run(cmd_args: &[String]) -> ExitStatus {
     let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
+        .args(cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    cmd.wait().unwrap()
 }
@@ -1,40 +1,263 @@
-use std::collections::HashSet;
+use std::{
+    collections::HashSet,
+    env,
+    process::{self, Command},
+    time::Duration,
+};
 
-use crate::template::run_multi::run_multi;
+use crate::template::history::{compare, History, HistoryEntry};
+use crate::template::run_multi::{run_multi, MultiRun, RunOptions};
+use crate::template::runner::BenchOptions;
+use crate::template::sources::source_hash;
+pub use crate::template::timings::MergePolicy;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
-
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
-
-    let days_to_run = day.map_or_else(
-        || {
-            if run_all {
-                all_days().collect()
-            } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
-                    .collect()
-            }
-        },
-        |day| HashSet::from([day]),
-    );
+use crate::template::{all_days, readme_benchmarks, Day, ANSI_BOLD, ANSI_RESET};
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+/// Options of `time --compare`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompareOptions {
+    /// Commit (prefix) of the stored run to compare against. Defaults to the latest run of each day.
+    pub baseline: Option<String>,
+    /// Slowdown in percent above which a part counts as regressed.
+    pub threshold: f64,
+}
 
-    if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+/// Days that `time` benches.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Selection {
+    /// A single day.
+    Day(Day),
+    /// All days (`--all`).
+    All,
+    /// Days whose source changed since their timings were stored (`--changed`).
+    Changed,
+    /// Days that do not have timings for both parts stored yet.
+    Incomplete,
+}
 
-        println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
-                println!("Stored updated benchmarks.");
-            }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+/// Bench solutions. If `store` is set, the timings are merged into the stored ones with that policy.
+/// With `memory`, solutions are built with the `count-alloc` feature to record their heap usage.
+/// Their runtimes are skewed by the instrumentation, so only the heap usage is stored.
+/// With `in_process`, all solutions are benched from a single binary via the solution registry.
+pub fn handle(
+    selection: Selection,
+    store: Option<MergePolicy>,
+    compare: Option<&CompareOptions>,
+    memory: bool,
+    in_process: bool,
+    bench: &BenchOptions,
+    timeout: Option<Duration>,
+) {
+    let stored_timings = match Timings::read_from_file() {
+        Ok(timings) => timings,
+        Err(e) if store.is_some() => {
+            eprintln!("Not storing benchmarks: {e}");
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Ignoring stored benchmarks: {e}");
+            Timings::default()
+        }
+    };
+
+    let days_to_run: HashSet<Day> = match selection {
+        Selection::Day(day) => HashSet::from([day]),
+        Selection::All => all_days().collect(),
+        Selection::Changed => all_days()
+            .filter(|day| is_changed(&stored_timings, *day))
+            .collect(),
+        Selection::Incomplete => all_days()
+            .filter(|day| !stored_timings.is_day_complete(*day))
+            .collect(),
+    };
+
+    if selection == Selection::Changed && days_to_run.is_empty() {
+        println!("No solution changed since its timings were stored.");
+        return;
+    }
+
+    let options = RunOptions {
+        is_release: true,
+        is_dhat: false,
+        count_alloc: memory,
+        bench: Some(bench.clone()),
+        timeout,
+    };
+
+    let mut run = if in_process {
+        run_in_process(&days_to_run, bench, timeout)
+    } else {
+        run_multi(&days_to_run, &options)
+    };
+
+    for timing in &mut run.timings.as_mut().unwrap().data {
+        timing.source_hash = source_hash(timing.day);
+    }
+
+    let timings = run.timings.as_ref().unwrap();
+
+    // NOTE: describe the revision before storing, since the readme update dirties the tree.
+    let entry = HistoryEntry::new(timings.clone());
+    let history = History::read_from_file();
+
+    let regressions = compare.map_or(0, |options| print_comparison(&history, &entry, options));
+
+    // runtimes measured with the counting allocator are skewed, so they are kept out of the history.
+    if !memory && run.failures().is_empty() && run.wrong_answers().is_empty() {
+        if let Err(e) = History::append(&entry) {
+            eprintln!("Failed to append to benchmark history: {e}");
+        }
+    }
+
+    if store.is_some() && !run.wrong_answers().is_empty() {
+        eprintln!("\nNot storing benchmarks because some answers are wrong.");
+    } else if memory && store.is_some() {
+        for timing in &timings.data {
+            if is_changed(&stored_timings, timing.day) {
+                eprintln!(
+                    "Day {}: no timings stored for the current source, store them without --memory first.",
+                    timing.day
+                );
             }
         }
+
+        let merged_timings = stored_timings.merge_alloc(timings);
+        merged_timings.store_file().unwrap();
+        update_readme(merged_timings);
+    } else if let Some(policy) = store {
+        // NOTE: `history` was read before appending, so it does not contain the current run.
+        let previous: Vec<&Timings> = history
+            .entries
+            .iter()
+            .filter(|e| e.host == entry.host)
+            .map(|e| &e.timings)
+            .collect();
+        let merged_timings = stored_timings.merge(timings, policy, &previous);
+        merged_timings.store_file().unwrap();
+        update_readme(merged_timings);
     }
+
+    run.exit_on_failures();
+
+    if regressions > 0 {
+        eprintln!(
+            "✗ {regressions} part(s) regressed by more than {}%.",
+            compare.map_or(0.0, |x| x.threshold)
+        );
+        process::exit(1);
+    }
+}
+
+/// Bench a set of days from this binary via the solution registry.
+#[cfg(feature = "registry")]
+fn run_in_process(
+    days_to_run: &HashSet<Day>,
+    bench: &BenchOptions,
+    timeout: Option<Duration>,
+) -> MultiRun {
+    use crate::template::registry;
+
+    // timings of debug builds are meaningless, so switch to a release build.
+    if cfg!(debug_assertions) {
+        rerun_in_process();
+    }
+
+    registry::run_days(days_to_run, timeout, Some(bench))
+}
+
+/// The registry is only compiled with the `registry` feature, so re-run this command with it enabled.
+#[cfg(not(feature = "registry"))]
+fn run_in_process(
+    _days_to_run: &HashSet<Day>,
+    _bench: &BenchOptions,
+    _timeout: Option<Duration>,
+) -> MultiRun {
+    rerun_in_process()
+}
+
+/// Re-run this command with the same arguments through cargo, as a release build with the `registry` feature.
+fn rerun_in_process() -> ! {
+    let status = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--release",
+            "--features",
+            "registry",
+            "--",
+        ])
+        .args(env::args().skip(1))
+        .status()
+        .unwrap();
+
+    process::exit(status.code().unwrap_or(1));
+}
+
+fn update_readme(timings: Timings) {
+    println!();
+    match readme_benchmarks::update(timings) {
+        Ok(()) => {
+            println!("Stored updated benchmarks.");
+        }
+        Err(_) => {
+            eprintln!("Failed to store updated benchmarks.");
+        }
+    }
+}
+
+/// Whether a day has a solution whose source hash differs from the one stored with its timing.
+fn is_changed(stored_timings: &Timings, day: Day) -> bool {
+    let Some(hash) = source_hash(day) else {
+        return false;
+    };
+
+    !stored_timings
+        .data
+        .iter()
+        .any(|t| t.day == day && t.source_hash.as_ref() == Some(&hash))
+}
+
+/// Print how the medians of the `current` run changed against the stored history.
+/// Only baselines measured on the same host are used.
+/// Returns the number of parts that regressed beyond the threshold.
+fn print_comparison(history: &History, current: &HistoryEntry, options: &CompareOptions) -> usize {
+    let timings = &current.timings;
+
+    let comparisons = match &options.baseline {
+        Some(commit) => {
+            let Some(entry) = history.find(commit, &current.host) else {
+                eprintln!(
+                    "No benchmark run stored for host {} matches commit {commit}.",
+                    current.host
+                );
+                process::exit(1);
+            };
+            compare(timings, |day| entry.timing(day))
+        }
+        None => compare(timings, |day| history.latest(day, &current.host)),
+    };
+
+    println!();
+    println!("{ANSI_BOLD}Comparison{ANSI_RESET}");
+
+    if comparisons.is_empty() {
+        println!(
+            "No benchmarks stored for host {} to compare against.",
+            current.host
+        );
+    }
+
+    for comparison in &comparisons {
+        let mark = if comparison.is_regression(options.threshold) {
+            "✗"
+        } else {
+            "✓"
+        };
+        println!("{mark} {comparison}");
+    }
+
+    comparisons
+        .iter()
+        .filter(|c| c.is_regression(options.threshold))
+        .count()
 }
@@ -0,0 +1,329 @@
+/// Test harness that checks solutions against the example answers declared in `data/examples`.
+///
+/// Expected answers live in a sidecar file `data/examples/<day>.answers.json`, keyed by the name of
+/// the example file without extension, so that adding a test case does not require touching code:
+///
+/// ```json
+/// {
+///   "06": { "part_1": 41, "part_2": 6 },
+///   "06-large": { "part_1": "5461" }
+/// }
+/// ```
+///
+/// An answer of `null` expects the part to return `None`, a missing key skips the part for that example.
+use std::{
+    collections::{BTreeMap, HashMap},
+    env,
+    fmt::{Display, Write},
+    fs, io,
+    path::Path,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, InputError};
+
+/// Largest integer that a JSON number represents exactly.
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// An example input together with the answer expected for one part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExampleCase {
+    /// Name of the example file without extension, e.g. `03-1`.
+    pub name: String,
+    /// Expected answer, `None` if the part is expected to return `None`.
+    pub expected: Option<String>,
+}
+
+/// Path of the example answers of a day, relative to the repository root.
+pub fn answers_path(day: Day) -> String {
+    format!("data/examples/{day}.answers.json")
+}
+
+/// Expected answers of the examples of a day, as declared in the sidecar file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExampleAnswers {
+    /// Expected answers keyed by example name and part. `None` expects the part to return `None`.
+    pub data: BTreeMap<String, BTreeMap<u8, Option<String>>>,
+}
+
+impl ExampleAnswers {
+    /// Rehydrate the example answers of a day from their sidecar file.
+    pub fn read_from_file(day: Day) -> Result<Self, String> {
+        let path = answers_path(day);
+        fs::read_to_string(&path)
+            .map_err(|e| format!("could not read {path}: {e}."))
+            .and_then(|json| {
+                ExampleAnswers::try_from(json).map_err(|e| format!("invalid {path}: {e}"))
+            })
+    }
+
+    /// Dehydrate the example answers of a day to their sidecar file.
+    pub fn store_file(&self, day: Day) -> Result<(), io::Error> {
+        fs::write(answers_path(day), self.to_json())
+    }
+
+    /// Get the expected answer of a part. Returns `None` if the example does not declare one.
+    pub fn get(&self, name: &str, part: u8) -> Option<Option<&str>> {
+        self.data
+            .get(name)?
+            .get(&part)
+            .map(|answer| answer.as_deref())
+    }
+
+    /// Declare the expected answer of a part, replacing any previous declaration.
+    pub fn set(&mut self, name: &str, part: u8, answer: Option<&str>) {
+        self.data
+            .entry(name.to_string())
+            .or_default()
+            .insert(part, answer.map(ToString::to_string));
+    }
+
+    /// Examples that declare an answer for `part`, sorted by name.
+    pub fn cases(&self, part: u8) -> Vec<ExampleCase> {
+        self.data
+            .iter()
+            .filter_map(|(name, parts)| {
+                Some(ExampleCase {
+                    name: name.clone(),
+                    expected: parts.get(&part)?.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// Serialize to JSON with one line per example, in a stable order so the file stays easy to edit.
+    pub(crate) fn to_json(&self) -> String {
+        let mut json = String::from("{\n");
+
+        for (i, (name, parts)) in self.data.iter().enumerate() {
+            let parts: Vec<String> = parts
+                .iter()
+                .map(|(part, answer)| {
+                    format!("\"part_{part}\": {}", answer_to_json(answer.as_deref()))
+                })
+                .collect();
+
+            let separator = if i + 1 < self.data.len() { "," } else { "" };
+            let _ = writeln!(
+                json,
+                "  {}: {{ {} }}{separator}",
+                JsonValue::String(name.clone())
+                    .stringify()
+                    .unwrap_or_default(),
+                parts.join(", ")
+            );
+        }
+
+        json.push_str("}\n");
+        json
+    }
+}
+
+/// Integer answers are written as JSON numbers, everything else as strings.
+fn answer_to_json(answer: Option<&str>) -> String {
+    match answer {
+        None => "null".into(),
+        Some(x)
+            if x.parse::<i64>()
+                .is_ok_and(|n| n.unsigned_abs() <= MAX_SAFE_INTEGER as u64) =>
+        {
+            x.into()
+        }
+        Some(x) => JsonValue::String(x.into()).stringify().unwrap_or_default(),
+    }
+}
+
+/// Load the cases that declare an answer for `part`, sorted by name.
+pub fn cases(day: Day, part: u8) -> Result<Vec<ExampleCase>, String> {
+    ExampleAnswers::read_from_file(day).map(|answers| answers.cases(part))
+}
+
+/// Run `func` against every example of `day` that declares an answer for `part`.
+///
+/// Skips with a message if the day has no sidecar file. Panics if the sidecar file is malformed,
+/// with a list of all mismatching examples, or if no example declares an answer for `part`.
+pub fn check<T: Display>(day: Day, part: u8, func: impl Fn(&str) -> Option<T>) {
+    let path = answers_path(day);
+
+    if !Path::new(&path).exists() {
+        println!("Skipped examples of part {part}: {path} does not exist.");
+        return;
+    }
+
+    let cases = cases(day, part).unwrap_or_else(|e| panic!("{e}"));
+
+    assert!(
+        !cases.is_empty(),
+        "no example declares an answer for part {part} in {}.",
+        answers_path(day)
+    );
+
+    let failures: Vec<String> = cases
+        .iter()
+        .filter_map(|case| {
+            let input = match read_example(day, &case.name) {
+                Ok(input) => input,
+                Err(e) => return Some(format!("{}: {e}", case.name)),
+            };
+
+            let answer = func(&input).map(|x| x.to_string());
+
+            (answer != case.expected).then(|| {
+                format!(
+                    "{}: expected {}, got {}",
+                    case.name,
+                    format_answer(case.expected.as_deref()),
+                    format_answer(answer.as_deref())
+                )
+            })
+        })
+        .collect();
+
+    assert!(
+        failures.is_empty(),
+        "part {part} failed {} of {} example(s):\n{}",
+        failures.len(),
+        cases.len(),
+        failures.join("\n")
+    );
+}
+
+fn read_example(day: Day, name: &str) -> Result<String, InputError> {
+    let path = env::current_dir()
+        .unwrap_or_default()
+        .join("data")
+        .join("examples")
+        .join(format!("{name}.txt"));
+
+    // NOTE: scaffolded example files start out empty, which is a valid example for unsolved parts.
+    match fs::read_to_string(&path) {
+        Ok(input) => Ok(input),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(InputError::Missing { path, day }),
+        Err(source) => Err(InputError::Unreadable { path, day, source }),
+    }
+}
+
+fn format_answer(answer: Option<&str>) -> String {
+    answer.map_or("None".into(), |x| format!("`{x}`"))
+}
+
+impl TryFrom<String> for ExampleAnswers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON."))?;
+
+        let examples = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let mut answers = ExampleAnswers::default();
+
+        for (name, value) in examples {
+            let json_parts = value.get::<HashMap<String, JsonValue>>().ok_or(format!(
+                "expected answers of example {name} to be an object."
+            ))?;
+
+            for part in [1, 2] {
+                let key = format!("part_{part}");
+
+                let expected = match json_parts.get(&key) {
+                    None => continue,
+                    Some(JsonValue::Null) => None,
+                    Some(JsonValue::String(x)) => Some(x.clone()),
+                    Some(JsonValue::Number(x))
+                        if x.fract() == 0.0 && x.abs() <= MAX_SAFE_INTEGER =>
+                    {
+                        #[allow(clippy::cast_possible_truncation)]
+                        Some((*x as i64).to_string())
+                    }
+                    Some(_) => {
+                        return Err(format!(
+                            "expected {name}.{key} to be null, a string or an integer."
+                        ))
+                    }
+                };
+
+                answers
+                    .data
+                    .entry(name.clone())
+                    .or_default()
+                    .insert(part, expected);
+            }
+        }
+
+        Ok(answers)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, ExampleAnswers, ExampleCase};
+    use crate::day;
+
+    fn parse_cases(json: &str, part: u8) -> Result<Vec<ExampleCase>, String> {
+        ExampleAnswers::try_from(json.to_string()).map(|answers| answers.cases(part))
+    }
+
+    fn case(name: &str, expected: Option<&str>) -> ExampleCase {
+        ExampleCase {
+            name: name.into(),
+            expected: expected.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn parses_cases_per_part() {
+        let json = r#"{
+            "03-2": { "part_2": "48" },
+            "03-1": { "part_1": 161, "part_2": null },
+            "03-large": { "part_1": "65601038650482" }
+        }"#;
+
+        assert_eq!(
+            parse_cases(json, 1).unwrap(),
+            vec![
+                case("03-1", Some("161")),
+                case("03-large", Some("65601038650482"))
+            ]
+        );
+        assert_eq!(
+            parse_cases(json, 2).unwrap(),
+            vec![case("03-1", None), case("03-2", Some("48"))]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_answers() {
+        assert!(parse_cases(r#"{ "01": { "part_1": 1.5 } }"#, 1).is_err());
+        assert!(parse_cases(r#"{ "01": { "part_1": [] } }"#, 1).is_err());
+        assert!(parse_cases(r#"{ "01": 1 }"#, 1).is_err());
+        assert!(parse_cases("[]", 1).is_err());
+    }
+
+    #[test]
+    fn skips_days_without_answers() {
+        check(day!(25), 1, |_| -> Option<u32> { panic!("should not run") });
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let mut answers = ExampleAnswers::default();
+        answers.set("01", 1, Some("11"));
+        answers.set("01", 2, None);
+        answers.set("01-large", 2, Some("4,6,3"));
+        answers.set("01-huge", 1, Some("65601038650482"));
+
+        let json = answers.to_json();
+        assert_eq!(
+            json,
+            "{\n  \"01\": { \"part_1\": 11, \"part_2\": null },\n  \"01-huge\": { \"part_1\": 65601038650482 },\n  \"01-large\": { \"part_2\": \"4,6,3\" }\n}\n"
+        );
+        assert_eq!(ExampleAnswers::try_from(json).unwrap(), answers);
+        assert_eq!(answers.get("01", 2), Some(None));
+        assert_eq!(answers.get("01", 3), None);
+    }
+}
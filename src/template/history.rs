@@ -0,0 +1,451 @@
+/// Append-only history of stored benchmark runs, used to detect regressions over time.
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{Error, Write},
+    process::Command,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    timings::{Timing, Timings},
+    Day,
+};
+
+static HISTORY_FILE_PATH: &str = "./data/timings_history.jsonl";
+
+/// A stored benchmark run, together with the revision and machine it was measured on.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Commit hash of `HEAD`, if run inside a git repository.
+    pub commit: Option<String>,
+    /// Whether tracked files had uncommitted changes.
+    pub dirty: bool,
+    pub host: String,
+    pub timings: Timings,
+}
+
+impl HistoryEntry {
+    /// Describe the current revision and machine for a set of timings.
+    pub fn new(timings: Timings) -> Self {
+        let (commit, dirty) = git_revision();
+
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |x| x.as_secs()),
+            commit,
+            dirty,
+            host: hostname(),
+            timings,
+        }
+    }
+
+    /// Timing of a day in this run.
+    pub fn timing(&self, day: Day) -> Option<&Timing> {
+        self.timings.data.iter().find(|t| t.day == day)
+    }
+}
+
+/// All stored benchmark runs, oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// Read the history from its JSON lines file. Lines that can not be parsed are skipped.
+    pub fn read_from_file() -> Self {
+        let entries = fs::read_to_string(HISTORY_FILE_PATH)
+            .unwrap_or_default()
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| HistoryEntry::try_from(line.to_string()).ok())
+            .collect();
+
+        Self { entries }
+    }
+
+    /// Append an entry to the history file without touching previous entries.
+    pub fn append(entry: &HistoryEntry) -> Result<(), Error> {
+        let line = JsonValue::from(entry)
+            .stringify()
+            .map_err(|e| Error::other(e.to_string()))?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(HISTORY_FILE_PATH)?;
+
+        writeln!(file, "{line}")
+    }
+
+    /// Latest entry measured on `host` whose commit starts with `commit`.
+    /// Timings of other machines are not comparable, so they are never used as a baseline.
+    pub fn find(&self, commit: &str, host: &str) -> Option<&HistoryEntry> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|e| e.host == host && e.commit.as_deref().is_some_and(|c| c.starts_with(commit)))
+    }
+
+    /// Latest timing of a day measured on `host`.
+    pub fn latest(&self, day: Day, host: &str) -> Option<&Timing> {
+        self.entries
+            .iter()
+            .rev()
+            .filter(|e| e.host == host)
+            .find_map(|e| e.timing(day))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Change in the median time of a part between a baseline and the current run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    pub day: Day,
+    /// `0` for the parse step, `1` or `2` for the parts.
+    pub part: u8,
+    pub before_nanos: f64,
+    pub after_nanos: f64,
+}
+
+impl Comparison {
+    /// Relative change in percent, positive if the part got slower.
+    pub fn change(&self) -> f64 {
+        (self.after_nanos - self.before_nanos) / self.before_nanos * 100.0
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change() > threshold
+    }
+}
+
+impl Display for Comparison {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let step = match self.part {
+            0 => "parse".to_string(),
+            part => format!("part {part}"),
+        };
+
+        write!(
+            f,
+            "Day {} {step}: {:.1?} → {:.1?} ({:+.1}%)",
+            self.day,
+            Duration::from_nanos(self.before_nanos as u64),
+            Duration::from_nanos(self.after_nanos as u64),
+            self.change()
+        )
+    }
+}
+
+/// Compare the median time of the parse step and every part in `current` against its baseline timing.
+pub fn compare<'a>(
+    current: &Timings,
+    baseline: impl Fn(Day) -> Option<&'a Timing>,
+) -> Vec<Comparison> {
+    let mut comparisons = vec![];
+
+    for timing in &current.data {
+        let Some(before) = baseline(timing.day) else {
+            continue;
+        };
+
+        for part in [0, 1, 2] {
+            let (Some(before_nanos), Some(after_nanos)) =
+                (median(before, part), median(timing, part))
+            else {
+                continue;
+            };
+
+            if before_nanos > 0.0 {
+                comparisons.push(Comparison {
+                    day: timing.day,
+                    part,
+                    before_nanos,
+                    after_nanos,
+                });
+            }
+        }
+    }
+
+    comparisons
+}
+
+fn median(timing: &Timing, part: u8) -> Option<f64> {
+    timing.part(part).map(|x| x.nanos)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Commit hash of `HEAD` and whether tracked files have uncommitted changes.
+fn git_revision() -> (Option<String>, bool) {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|x| x.status.success())
+            .map(|x| String::from_utf8_lossy(&x.stdout).trim().to_string())
+    };
+
+    let commit = git(&["rev-parse", "HEAD"]);
+    let dirty = commit.is_some()
+        && git(&["status", "--porcelain", "--untracked-files=no"]).is_some_and(|x| !x.is_empty());
+
+    (commit, dirty)
+}
+
+fn hostname() -> String {
+    Command::new("hostname")
+        .output()
+        .ok()
+        .map(|x| String::from_utf8_lossy(&x.stdout).trim().to_string())
+        .filter(|x| !x.is_empty())
+        .or_else(|| env::var("HOSTNAME").ok())
+        .or_else(|| env::var("COMPUTERNAME").ok())
+        .unwrap_or_else(|| "unknown".into())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryEntry> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("dirty".into(), JsonValue::Boolean(value.dirty));
+        map.insert("host".into(), JsonValue::String(value.host.clone()));
+        map.insert(
+            "timings".into(),
+            JsonValue::Array(value.timings.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for HistoryEntry {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected history entry to be an object.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("expected entry.timestamp to be a number.")?;
+
+        let commit = match json.get("commit") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("expected entry.commit to be null or a string.")?
+                    .clone(),
+            ),
+        };
+
+        let timings = json
+            .get("timings")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("expected entry.timings to be an array.")?;
+
+        Ok(HistoryEntry {
+            timestamp: *timestamp as u64,
+            commit,
+            dirty: json
+                .get("dirty")
+                .and_then(|v| v.get::<bool>().copied())
+                .unwrap_or_default(),
+            host: json
+                .get("host")
+                .and_then(|v| v.get::<String>().cloned())
+                .unwrap_or_default(),
+            timings: Timings {
+                data: timings
+                    .iter()
+                    .map(Timing::try_from)
+                    .collect::<Result<_, _>>()?,
+            },
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, History, HistoryEntry};
+    use crate::{
+        day,
+        template::{
+            stats::Stats,
+            timings::{PartTiming, Timing, Timings},
+        },
+    };
+    use tinyjson::JsonValue;
+
+    fn stats(median: f64) -> Stats {
+        Stats {
+            samples: 10,
+            warmup: 1,
+            mean: median,
+            median,
+            min: median,
+            max: median,
+            stddev: 0.0,
+            ci_low: median,
+            ci_high: median,
+            outliers: 0,
+        }
+    }
+
+    fn part(median: f64) -> PartTiming {
+        PartTiming {
+            nanos: median,
+            samples: 10,
+            stats: Some(stats(median)),
+            alloc: None,
+        }
+    }
+
+    fn timing(day: u8, part_1: Option<f64>, part_2: Option<f64>) -> Timing {
+        Timing {
+            day: crate::template::Day::new(day).unwrap(),
+            parse: None,
+            part_1: part_1.map(part),
+            part_2: part_2.map(part),
+            source_hash: None,
+        }
+    }
+
+    fn entry(commit: &str, timings: Vec<Timing>) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: 1,
+            commit: Some(commit.into()),
+            dirty: false,
+            host: "host".into(),
+            timings: Timings { data: timings },
+        }
+    }
+
+    fn entry_on(host: &str, commit: &str, timings: Vec<Timing>) -> HistoryEntry {
+        HistoryEntry {
+            host: host.into(),
+            ..entry(commit, timings)
+        }
+    }
+
+    #[test]
+    fn roundtrips_entries() {
+        let entry = entry("abc", vec![timing(1, Some(100.0), None)]);
+        let line = JsonValue::from(&entry).stringify().unwrap();
+        let parsed = HistoryEntry::try_from(line).unwrap();
+        assert_eq!(parsed.commit, Some("abc".into()));
+        assert_eq!(parsed.host, "host");
+        assert_eq!(parsed.timings.data[0].part_1, Some(part(100.0)));
+    }
+
+    #[test]
+    fn finds_baselines() {
+        let history = History {
+            entries: vec![
+                entry("aaa111", vec![timing(1, Some(100.0), None)]),
+                entry("bbb222", vec![timing(2, Some(200.0), None)]),
+            ],
+        };
+
+        assert_eq!(
+            history.find("aaa", "host").unwrap().timings.data[0].day,
+            day!(1)
+        );
+        assert!(history.find("ccc", "host").is_none());
+        assert_eq!(
+            history.latest(day!(1), "host").unwrap().part_1,
+            Some(part(100.0))
+        );
+        assert!(history.latest(day!(3), "host").is_none());
+    }
+
+    #[test]
+    fn only_uses_baselines_of_the_same_host() {
+        let history = History {
+            entries: vec![
+                entry_on("laptop", "aaa111", vec![timing(1, Some(100.0), None)]),
+                entry_on("ci", "aaa111", vec![timing(1, Some(300.0), None)]),
+            ],
+        };
+
+        let latest = |host| history.latest(day!(1), host).map(|t| t.part_1.clone());
+        assert_eq!(latest("laptop"), Some(Some(part(100.0))));
+        assert_eq!(latest("ci"), Some(Some(part(300.0))));
+        assert_eq!(latest("desktop"), None);
+        assert_eq!(history.find("aaa", "laptop").unwrap().host, "laptop");
+        assert!(history.find("aaa", "desktop").is_none());
+    }
+
+    #[test]
+    fn detects_regressions() {
+        let baseline = Timings {
+            data: vec![timing(1, Some(100.0), Some(200.0))],
+        };
+        let current = Timings {
+            data: vec![
+                timing(1, Some(120.0), Some(190.0)),
+                timing(2, Some(10.0), None),
+            ],
+        };
+
+        let comparisons = compare(&current, |day| baseline.data.iter().find(|t| t.day == day));
+
+        assert_eq!(comparisons.len(), 2);
+        assert!((comparisons[0].change() - 20.0).abs() < 1e-9);
+        assert!(comparisons[0].is_regression(10.0));
+        assert!(!comparisons[0].is_regression(25.0));
+        assert!(!comparisons[1].is_regression(0.0));
+    }
+
+    #[test]
+    fn compares_the_parse_step() {
+        let with_parse = |nanos| Timing {
+            parse: Some(part(nanos)),
+            ..timing(1, None, None)
+        };
+        let baseline = with_parse(100.0);
+        let current = Timings {
+            data: vec![with_parse(150.0)],
+        };
+
+        let comparisons = compare(&current, |_| Some(&baseline));
+
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].part, 0);
+        assert!(comparisons[0].is_regression(10.0));
+        assert!(comparisons[0].to_string().starts_with("Day 01 parse:"));
+    }
+}
@@ -0,0 +1,393 @@
+/// Fallible loading of puzzle inputs and examples from the `data` directory.
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::template::Day;
+
+/// Flag that makes a solution read its input from a path, or from stdin if the path is `-`.
+pub const INPUT_FLAG: &str = "--input";
+
+/// Flag that makes a solution read the named input at `data/inputs/<day>/<name>.txt`.
+pub const INPUT_NAME_FLAG: &str = "--input-name";
+
+/// Flag that makes a solution read its input from `data/examples`.
+pub const EXAMPLE_FLAG: &str = "--example";
+
+/// Path that stands for stdin when passed to `--input`.
+const STDIN_PATH: &str = "-";
+
+/// Reasons why an input file could not be loaded.
+#[derive(Debug)]
+pub enum InputError {
+    /// The file does not exist.
+    Missing { path: PathBuf, day: Day },
+    /// The file exists, but contains only whitespace.
+    Empty { path: PathBuf, day: Day },
+    /// The file exists, but could not be read.
+    Unreadable {
+        path: PathBuf,
+        day: Day,
+        source: io::Error,
+    },
+}
+
+impl InputError {
+    /// Resolved path of the file that could not be loaded.
+    pub fn path(&self) -> &Path {
+        match self {
+            InputError::Missing { path, .. }
+            | InputError::Empty { path, .. }
+            | InputError::Unreadable { path, .. } => path,
+        }
+    }
+
+    /// Short description of the error, used when reporting the parts that could not run.
+    pub fn reason(&self) -> &'static str {
+        match self {
+            InputError::Missing { .. } => "missing input",
+            InputError::Empty { .. } => "empty input",
+            InputError::Unreadable { .. } => "unreadable input",
+        }
+    }
+
+    /// Suggested fix for missing or empty puzzle inputs.
+    fn hint(&self) -> Option<String> {
+        let is_input = self
+            .path()
+            .parent()
+            .and_then(Path::file_name)
+            .is_some_and(|folder| folder == "inputs");
+
+        match self {
+            InputError::Missing { day, .. } | InputError::Empty { day, .. } if is_input => {
+                Some(format!("run `cargo download {day}` to download it."))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = if self.path() == Path::new(STDIN_PATH) {
+            "stdin".to_string()
+        } else {
+            format!("input file {}", self.path().display())
+        };
+
+        match self {
+            InputError::Missing { .. } => write!(f, "{name} does not exist.")?,
+            InputError::Empty { .. } => write!(f, "{name} is empty.")?,
+            InputError::Unreadable { source, .. } => {
+                write!(f, "{name} could not be read: {source}.")?;
+            }
+        }
+
+        if let Some(hint) = self.hint() {
+            write!(f, "\nhint: {hint}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputError::Unreadable { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Where a solution reads its input from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input at `data/inputs/<day>.txt`.
+    #[default]
+    Puzzle,
+    /// The example at `data/examples/<day>.txt`, or `<day>-<part>.txt` for parts that have their own example.
+    Example,
+    /// A named input at `data/inputs/<day>/<name>.txt`, e.g. the puzzle input of a teammate.
+    Named(String),
+    /// A file at an arbitrary path, relative to the working directory.
+    File(PathBuf),
+    /// Standard input.
+    Stdin,
+}
+
+/// Input of a solution, together with the parts that should run against it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LoadedInput {
+    pub input: String,
+    pub parts: Vec<u8>,
+}
+
+impl InputSource {
+    /// Create an input source from the `--input <path>`, `--input-name <name>` and `--example` options.
+    pub fn new(path: Option<String>, name: Option<String>, example: bool) -> Result<Self, String> {
+        match (path, name, example) {
+            (None, None, false) => Ok(InputSource::Puzzle),
+            (Some(path), None, false) if path == STDIN_PATH => Ok(InputSource::Stdin),
+            (Some(path), None, false) => Ok(InputSource::File(path.into())),
+            (None, Some(name), false) => Ok(InputSource::Named(name)),
+            (None, None, true) => Ok(InputSource::Example),
+            _ => Err(format!(
+                "only one of {INPUT_FLAG}, {INPUT_NAME_FLAG} and {EXAMPLE_FLAG} can be used."
+            )),
+        }
+    }
+
+    /// Whether answers for this input are kept in `data/answers.json`.
+    pub fn has_stored_answers(&self) -> bool {
+        matches!(self, InputSource::Puzzle | InputSource::Named(_))
+    }
+
+    /// Name of a named input, `None` for all other sources.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            InputSource::Named(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Read the input source from the arguments of the current process. Exits if `--input` has no value.
+    pub fn from_env() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        let value = |flag: &str| {
+            args.iter().position(|x| x == flag).map(|i| {
+                args.get(i + 1).cloned().unwrap_or_else(|| {
+                    eprintln!("{flag} expects a value.");
+                    process::exit(1);
+                })
+            })
+        };
+
+        InputSource::new(
+            value(INPUT_FLAG),
+            value(INPUT_NAME_FLAG),
+            args.iter().any(|x| x == EXAMPLE_FLAG),
+        )
+        .unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        })
+    }
+
+    /// Arguments that pass this input source on to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            InputSource::Puzzle => vec![],
+            InputSource::Example => vec![EXAMPLE_FLAG.into()],
+            InputSource::Named(name) => vec![INPUT_NAME_FLAG.into(), name.clone()],
+            InputSource::File(path) => vec![INPUT_FLAG.into(), path.display().to_string()],
+            InputSource::Stdin => vec![INPUT_FLAG.into(), STDIN_PATH.into()],
+        }
+    }
+
+    /// Load the input of `parts`. Examples may load a separate input per part.
+    pub fn load(&self, day: Day, parts: &[u8]) -> Result<Vec<LoadedInput>, InputError> {
+        let input = match self {
+            InputSource::Puzzle => try_read_file("inputs", day)?,
+            InputSource::Example => return load_examples(day, parts),
+            InputSource::Named(name) => {
+                read_data_file(&format!("inputs/{day}"), &format!("{name}.txt"), day)?
+            }
+            InputSource::File(path) => {
+                read_path(env::current_dir().unwrap_or_default().join(path), day)?
+            }
+            InputSource::Stdin => read_stdin(day)?,
+        };
+
+        Ok(vec![LoadedInput {
+            input,
+            parts: parts.to_vec(),
+        }])
+    }
+}
+
+/// Names of the named inputs of a day, i.e. the files in `data/inputs/<day>/`, sorted.
+pub fn named_inputs(day: Day) -> Vec<String> {
+    let cwd = env::current_dir().unwrap_or_default();
+    let dir = cwd.join("data").join("inputs").join(day.to_string());
+
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let name = entry.file_name().into_string().ok()?;
+                    name.strip_suffix(".txt").map(ToString::to_string)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    names.sort_unstable();
+    names
+}
+
+/// Parts with a `<day>-<part>.txt` example use it, all other parts share `<day>.txt`.
+fn load_examples(day: Day, parts: &[u8]) -> Result<Vec<LoadedInput>, InputError> {
+    let mut inputs: Vec<LoadedInput> = vec![];
+    let mut shared_parts: Vec<u8> = vec![];
+
+    for &part in parts {
+        match try_read_file_part("examples", day, part) {
+            Ok(input) => inputs.push(LoadedInput {
+                input,
+                parts: vec![part],
+            }),
+            Err(InputError::Missing { .. }) => shared_parts.push(part),
+            Err(e) => return Err(e),
+        }
+    }
+
+    if !shared_parts.is_empty() {
+        inputs.push(LoadedInput {
+            input: try_read_file("examples", day)?,
+            parts: shared_parts,
+        });
+    }
+
+    inputs.sort_by_key(|x| x.parts[0]);
+    Ok(inputs)
+}
+
+fn read_stdin(day: Day) -> Result<String, InputError> {
+    let path = PathBuf::from(STDIN_PATH);
+    let mut input = String::new();
+
+    match io::stdin().read_to_string(&mut input) {
+        Ok(_) if input.trim().is_empty() => Err(InputError::Empty { path, day }),
+        Ok(_) => Ok(input),
+        Err(source) => Err(InputError::Unreadable { path, day, source }),
+    }
+}
+
+/// Read a text file from `data/<folder>/<day>.txt`.
+pub fn try_read_file(folder: &str, day: Day) -> Result<String, InputError> {
+    read_data_file(folder, &format!("{day}.txt"), day)
+}
+
+/// Read a text file from `data/<folder>/<day>-<part>.txt`.
+pub fn try_read_file_part(folder: &str, day: Day, part: u8) -> Result<String, InputError> {
+    read_data_file(folder, &format!("{day}-{part}.txt"), day)
+}
+
+fn read_data_file(folder: &str, filename: &str, day: Day) -> Result<String, InputError> {
+    let cwd = env::current_dir().unwrap_or_default();
+    let path = cwd.join("data").join(folder).join(filename);
+    read_path(path, day)
+}
+
+fn read_path(path: PathBuf, day: Day) -> Result<String, InputError> {
+    match fs::read_to_string(&path) {
+        Ok(content) if content.trim().is_empty() => Err(InputError::Empty { path, day }),
+        Ok(content) => Ok(content),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(InputError::Missing { path, day }),
+        Err(source) => Err(InputError::Unreadable { path, day, source }),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{read_path, InputError, InputSource};
+    use crate::day;
+    use std::{env, fs};
+
+    #[test]
+    fn reports_missing_inputs() {
+        let path = env::temp_dir()
+            .join("aoc-input-test")
+            .join("inputs")
+            .join("06.txt");
+        let err = read_path(path.clone(), day!(6)).unwrap_err();
+
+        assert!(matches!(err, InputError::Missing { .. }));
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "input file {} does not exist.\nhint: run `cargo download 06` to download it.",
+                path.display()
+            )
+        );
+    }
+
+    #[test]
+    fn reports_empty_inputs() {
+        let dir = env::temp_dir().join("aoc-input-test-empty").join("inputs");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("07.txt");
+        fs::write(&path, "\n").unwrap();
+
+        let err = read_path(path, day!(7)).unwrap_err();
+        assert!(matches!(err, InputError::Empty { .. }));
+        assert!(err.to_string().contains("cargo download 07"));
+    }
+
+    #[test]
+    fn reports_unreadable_inputs() {
+        let dir = env::temp_dir()
+            .join("aoc-input-test-unreadable")
+            .join("examples");
+        fs::create_dir_all(dir.join("08.txt")).unwrap();
+
+        let err = read_path(dir.join("08.txt"), day!(8)).unwrap_err();
+        assert!(matches!(err, InputError::Unreadable { .. }));
+        assert!(!err.to_string().contains("hint"));
+    }
+
+    #[test]
+    fn parses_input_sources() {
+        assert_eq!(InputSource::new(None, None, false), Ok(InputSource::Puzzle));
+        assert_eq!(InputSource::new(None, None, true), Ok(InputSource::Example));
+        assert_eq!(
+            InputSource::new(Some("-".into()), None, false),
+            Ok(InputSource::Stdin)
+        );
+        assert_eq!(
+            InputSource::new(Some("edge.txt".into()), None, false),
+            Ok(InputSource::File("edge.txt".into()))
+        );
+        assert_eq!(
+            InputSource::new(None, Some("alice".into()), false),
+            Ok(InputSource::Named("alice".into()))
+        );
+        assert!(InputSource::new(Some("edge.txt".into()), None, true).is_err());
+        assert!(InputSource::new(None, Some("alice".into()), true).is_err());
+    }
+
+    #[test]
+    fn roundtrips_input_source_args() {
+        for source in [
+            InputSource::Puzzle,
+            InputSource::Example,
+            InputSource::Stdin,
+            InputSource::Named("alice".into()),
+            InputSource::File("data/edge.txt".into()),
+        ] {
+            let args = source.to_args();
+            let value = |flag: &str| {
+                args.iter()
+                    .position(|x| x == flag)
+                    .map(|i| args[i + 1].clone())
+            };
+            let example = args.contains(&"--example".to_string());
+            assert_eq!(
+                InputSource::new(value("--input"), value("--input-name"), example),
+                Ok(source)
+            );
+        }
+    }
+}
@@ -1,44 +1,66 @@
-use std::{env, fs};
-
+pub mod alloc;
 pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod examples;
 pub mod runner;
 
 pub use day::*;
+pub use input::{try_read_file, try_read_file_part, InputError, InputSource, LoadedInput};
 
+mod answers;
+mod checksums;
 mod day;
+mod history;
+mod input;
+mod puzzle;
 mod readme_benchmarks;
+#[cfg(feature = "registry")]
+pub mod registry;
+mod report;
 mod run_multi;
+mod sources;
+mod stats;
+mod submissions;
+mod summary;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads a text file to a string. Panics with a diagnostic if the file can not be read.
+///
+/// Unlike [`try_read_file`], empty files are accepted, since scaffolded example files start out empty.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    allow_empty(try_read_file(folder, day))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    allow_empty(try_read_file_part(folder, day, part))
+}
+
+fn allow_empty(result: Result<String, InputError>) -> String {
+    match result {
+        Ok(content) => content,
+        Err(InputError::Empty { .. }) => String::new(),
+        Err(e) => panic!("{e}"),
+    }
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// The generated `main` reads the puzzle input, unless `--input <path>`, `--input -` (stdin) or
+/// `--example` is passed to the binary.
+///
+/// Pass `parse = <fn>` to parse the input once and hand the parsed value to both parts, e.g.
+/// `solution!(6, parse = parse_input)`. The parse step is timed separately from the parts, which
+/// then take a reference to the parsed value instead of `&str`.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,8 +72,92 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, 1, parse = $parse:expr) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_one, 1]);
+    };
+    ($day:expr, 2, parse = $parse:expr) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_two, 2]);
+    };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@prelude $day);
+
+        /// Runs the solution against `input`, returning `false` if a part failed or an answer is wrong.
+        pub fn run_solution(input: &str) -> bool {
+            run_parts(input, &[$($part),*])
+        }
+
+        /// Runs the given `parts` of the solution against `input`.
+        fn run_parts(input: &str, parts: &[u8]) -> bool {
+            use $crate::template::runner::*;
+            let mut is_correct = true;
+            $(
+                if parts.contains(&$part) {
+                    is_correct &= run_part($func, input, DAY, $part);
+                }
+            )*
+            is_correct
+        }
+
+        $crate::solution!(@main $($part)*);
+    };
+
+    (@impl_parsed $day:expr, $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@prelude $day);
+
+        /// Runs the solution against `input`, returning `false` if a part failed or an answer is wrong.
+        pub fn run_solution(input: &str) -> bool {
+            run_parts(input, &[$($part),*])
+        }
+
+        /// Runs the given `parts` of the solution against `input`, parsing it once.
+        fn run_parts(input: &str, parts: &[u8]) -> bool {
+            use $crate::template::runner::*;
+            let Some(parsed) = run_parse($parse, input, DAY) else {
+                parts
+                    .iter()
+                    .for_each(|part| fail_part(DAY, *part, "skipped: the parse step failed"));
+                return false;
+            };
+            let mut is_correct = true;
+            $(
+                if parts.contains(&$part) {
+                    is_correct &= run_part($func, &parsed, DAY, $part);
+                }
+            )*
+            is_correct
+        }
+
+        $crate::solution!(@main $($part)*);
+    };
+
+    (@main $($part:expr)*) => {
+        // NOTE: unused when the solution is compiled into the in-process registry.
+        #[allow(dead_code)]
+        fn main() {
+            let source = $crate::template::InputSource::from_env();
+            let inputs = match source.load(DAY, &[$($part),*]) {
+                Ok(inputs) => inputs,
+                Err(e) => {
+                    eprintln!("{e}");
+                    $( $crate::template::runner::fail_part(DAY, $part, e.reason()); )*
+                    std::process::exit(1);
+                }
+            };
+            let mut is_correct = true;
+            for input in &inputs {
+                is_correct &= run_parts(&input.input, &input.parts);
+            }
+            if !is_correct {
+                std::process::exit(1);
+            }
+        }
+    };
+
+    (@prelude $day:expr) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -59,10 +165,8 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
-        fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
-        }
+        #[cfg(feature = "count-alloc")]
+        #[global_allocator]
+        static ALLOC: $crate::template::alloc::CountingAlloc = $crate::template::alloc::CountingAlloc;
     };
 }
@@ -0,0 +1,211 @@
+/// Heuristic parsing of the puzzle descriptions that `aoc-cli` downloads to `data/puzzles`.
+use std::{fs, io};
+
+use regex::Regex;
+
+use crate::template::{aoc_cli::get_puzzle_path, Day};
+
+/// A fenced code block of a puzzle description.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeBlock {
+    pub content: String,
+    /// Part of the puzzle whose description contains the block.
+    pub part: u8,
+    /// Whether the paragraph before the block mentions an example.
+    pub follows_example_text: bool,
+}
+
+/// Code blocks and example answers extracted from a puzzle description.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Puzzle {
+    pub blocks: Vec<CodeBlock>,
+    /// Answers to the example, guessed from the last emphasized value of each part's description.
+    pub example_answers: [Option<String>; 2],
+    /// Confirmed answers to the puzzle input, from the "Your puzzle answer was" line of each solved part.
+    pub puzzle_answers: [Option<String>; 2],
+}
+
+impl Puzzle {
+    /// Read and parse the puzzle description of a day.
+    pub fn read_from_file(day: Day) -> Result<Self, io::Error> {
+        fs::read_to_string(get_puzzle_path(day)).map(|md| Puzzle::parse(&md))
+    }
+
+    /// Parse a puzzle description in markdown.
+    pub fn parse(md: &str) -> Self {
+        let mut puzzle = Puzzle::default();
+        let mut part: u8 = 1;
+        let mut block: Option<Vec<&str>> = None;
+        let mut follows_example_text = false;
+        let mut emphasized: [Vec<String>; 2] = [vec![], vec![]];
+
+        for line in md.lines() {
+            if line.trim_start().starts_with("```") {
+                match block.take() {
+                    Some(lines) => {
+                        // NOTE: blocks in downloaded puzzles often end with an empty line.
+                        let mut content = lines.join("\n").trim_end_matches('\n').to_string();
+                        content.push('\n');
+                        puzzle.blocks.push(CodeBlock {
+                            content,
+                            part,
+                            follows_example_text,
+                        });
+                    }
+                    None => block = Some(vec![]),
+                }
+                continue;
+            }
+
+            if let Some(lines) = block.as_mut() {
+                lines.push(line);
+                continue;
+            }
+
+            if line.contains("--- Part Two ---") {
+                part = 2;
+                continue;
+            }
+
+            if let Some(answer) = puzzle_answer(line) {
+                puzzle.puzzle_answers[usize::from(part - 1)] = Some(answer);
+                continue;
+            }
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            follows_example_text = line.to_lowercase().contains("example");
+            emphasized[usize::from(part - 1)].extend(emphasized_values(line));
+        }
+
+        puzzle.example_answers = emphasized.map(|values| values.last().cloned());
+        puzzle
+    }
+
+    /// Index of the block that most likely holds the example input: the first block of part one
+    /// introduced by a paragraph that mentions an example, else the first block.
+    pub fn suggested_example(&self) -> Option<usize> {
+        self.blocks
+            .iter()
+            .position(|b| b.part == 1 && b.follows_example_text)
+            .or_else(|| (!self.blocks.is_empty()).then_some(0))
+    }
+}
+
+/// Answer in a line like ``Your puzzle answer was `1110981`.``
+fn puzzle_answer(line: &str) -> Option<String> {
+    let answer = line.trim().strip_prefix("Your puzzle answer was")?;
+    let answer = answer.trim().trim_end_matches('.').trim_matches('`').trim();
+    (!answer.is_empty()).then(|| answer.to_string())
+}
+
+/// Values that are emphasized in a line and look like answers.
+///
+/// Answers in puzzle descriptions are emphasized code, e.g. `` `*11*` ``. Emphasized prose is only
+/// considered if it contains a digit, to skip words like `*not*`.
+fn emphasized_values(line: &str) -> Vec<String> {
+    let code = Regex::new(r"`\*{1,2}([^*`]+)\*{1,2}`|\*{1,2}`([^*`]+)`\*{1,2}").unwrap();
+    let prose = Regex::new(r"\*{1,2}([^*\s]+)\*{1,2}").unwrap();
+
+    let values: Vec<String> = code
+        .captures_iter(line)
+        .filter_map(|c| c.get(1).or_else(|| c.get(2)))
+        .map(|m| m.as_str().to_string())
+        .collect();
+
+    if !values.is_empty() {
+        return values;
+    }
+
+    prose
+        .captures_iter(line)
+        .map(|c| c[1].to_string())
+        .filter(|x| x.chars().any(|c| c.is_ascii_digit()))
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{emphasized_values, Puzzle};
+
+    const PUZZLE: &str = r"\--- Day 1: Historian Hysteria ---
+----------
+
+Throughout the Chief's office, the historically significant locations are listed:
+
+```
+1 2
+
+```
+
+For example:
+
+```
+3   4
+4   3
+2   5
+```
+
+Then, add up the distances: `2 + 1 + 0`, a *total distance* of `*3*`!
+
+Your puzzle answer was `1110981`.
+
+\--- Part Two ---
+----------
+
+So, for these example lists, the similarity score at the end of this process is `*31*`.
+
+Your puzzle answer was `24869388`.
+";
+
+    #[test]
+    fn extracts_code_blocks() {
+        let puzzle = Puzzle::parse(PUZZLE);
+        assert_eq!(puzzle.blocks.len(), 2);
+        assert_eq!(puzzle.blocks[0].content, "1 2\n");
+        assert!(!puzzle.blocks[0].follows_example_text);
+        assert_eq!(puzzle.blocks[1].content, "3   4\n4   3\n2   5\n");
+        assert!(puzzle.blocks[1].follows_example_text);
+        assert_eq!(puzzle.suggested_example(), Some(1));
+    }
+
+    #[test]
+    fn extracts_example_answers() {
+        let puzzle = Puzzle::parse(PUZZLE);
+        assert_eq!(
+            puzzle.example_answers,
+            [Some("3".to_string()), Some("31".to_string())]
+        );
+    }
+
+    #[test]
+    fn extracts_puzzle_answers() {
+        let puzzle = Puzzle::parse(PUZZLE);
+        assert_eq!(
+            puzzle.puzzle_answers,
+            [Some("1110981".to_string()), Some("24869388".to_string())]
+        );
+
+        let unsolved = Puzzle::parse("Your puzzle answer was `4,6,3`.\n\n\\--- Part Two ---\n");
+        assert_eq!(unsolved.puzzle_answers, [Some("4,6,3".to_string()), None]);
+    }
+
+    #[test]
+    fn handles_missing_part_two() {
+        let puzzle = Puzzle::parse("For example:\n\n```\n1\n```\n\nThe result is **42**.\n");
+        assert_eq!(puzzle.example_answers, [Some("42".to_string()), None]);
+        assert_eq!(puzzle.suggested_example(), Some(0));
+        assert_eq!(Puzzle::parse("").suggested_example(), None);
+    }
+
+    #[test]
+    fn prefers_emphasized_code() {
+        assert_eq!(emphasized_values("the *total* is `*11*`"), vec!["11"]);
+        assert_eq!(emphasized_values("**4,6,3** and *not* this"), vec!["4,6,3"]);
+        assert!(emphasized_values("*only* prose").is_empty());
+    }
+}
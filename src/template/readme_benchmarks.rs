@@ -2,7 +2,8 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::template::timings::Timings;
+use crate::template::alloc::format_bytes;
+use crate::template::timings::{PartTiming, Timing, Timings};
 use crate::template::Day;
 
 static MARKER: &str = "<!--- benchmarking table --->";
@@ -55,22 +56,51 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
 fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    // only show the parse column if at least one solution parses its input separately.
+    let has_parse = timings.data.iter().any(|t| t.parse.is_some());
+    // only show the memory column if at least one solution was benched with `--memory`.
+    let has_memory = timings.data.iter().any(|t| peak_bytes(t).is_some());
+
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+
+    let mut columns = vec!["Day"];
+    if has_parse {
+        columns.push("Parse");
+    }
+    columns.extend(["Part 1", "Part 2"]);
+    if has_memory {
+        columns.push("Peak memory");
+    }
+
+    lines.push(format!("| {} |", columns.join(" | ")));
+    lines.push(format!(
+        "|{} :---:  |",
+        " :---: |".repeat(columns.len() - 1)
+    ));
 
     for timing in timings.data {
         let path = get_path_for_bin(timing.day);
+        let parse = if has_parse {
+            format!(" `{}` |", format_part(timing.parse.as_ref()))
+        } else {
+            String::new()
+        };
+        let memory = if has_memory {
+            format!(
+                " `{}` |",
+                peak_bytes(&timing).map_or_else(|| "-".into(), format_bytes)
+            )
+        } else {
+            String::new()
+        };
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) |{} `{}` | `{}` |{}",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            parse,
+            format_part(timing.part_1.as_ref()),
+            format_part(timing.part_2.as_ref()),
+            memory
         ));
     }
 
@@ -81,6 +111,19 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
     lines.join("\n")
 }
 
+fn format_part(timing: Option<&PartTiming>) -> String {
+    timing.map_or_else(|| "-".into(), ToString::to_string)
+}
+
+/// Largest peak heap usage of the parse step and both parts, if measured.
+fn peak_bytes(timing: &Timing) -> Option<u64> {
+    [&timing.parse, &timing.part_1, &timing.part_2]
+        .into_iter()
+        .flatten()
+        .filter_map(|x| x.alloc.map(|alloc| alloc.peak_bytes))
+        .max()
+}
+
 fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
     let positions = locate_table(s)?;
     let table = construct_table("##", timings, total_millis);
@@ -100,28 +143,46 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{
+        day,
+        template::{
+            alloc::AllocStats,
+            timings::{PartTiming, Timing, Timings},
+        },
+    };
+
+    fn part(millis: f64) -> Option<PartTiming> {
+        Some(PartTiming {
+            nanos: millis * 1e6,
+            samples: 10,
+            stats: None,
+            alloc: None,
+        })
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    parse: None,
+                    part_1: part(10.0),
+                    part_2: part(20.0),
+                    source_hash: None,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    parse: None,
+                    part_1: part(30.0),
+                    part_2: part(40.0),
+                    source_hash: None,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
+                    parse: None,
+                    part_1: part(40.0),
+                    part_2: part(50.0),
+                    source_hash: None,
                 },
             ],
         }
@@ -169,9 +230,9 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +241,61 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_parse() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = part(5.0);
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `-` | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `5.0ms` | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `-` | `40.0ms` | `50.0ms` |",
+            "",
+            "**Total: 190.00ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_benchmarks_with_memory() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1.as_mut().unwrap().alloc = Some(AllocStats {
+            allocations: 2,
+            total_bytes: 4096,
+            peak_bytes: 1536,
+        });
+        timings.data[0].part_2.as_mut().unwrap().alloc = Some(AllocStats {
+            allocations: 1,
+            total_bytes: 512,
+            peak_bytes: 512,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Part 1 | Part 2 | Peak memory |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` | `1.5 KiB` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` | `-` |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` | `-` |",
+            "",
+            "**Total: 190.00ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
 }
@@ -0,0 +1,186 @@
+/// In-process registry of all solutions, allowing every day to run from a single binary.
+///
+/// Enabled by the `registry` feature, which compiles each `src/bin/NN.rs` into the library as a
+/// module (see `build.rs`). The per-day binaries keep working independently of this.
+use std::{
+    collections::HashSet,
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Mutex, PoisonError,
+    },
+    thread,
+    time::Duration,
+};
+
+use crate::template::{
+    report::{Report, Status},
+    run_multi::{child_commands::timing_from_reports, MultiRun},
+    runner::{fail_part, set_in_process_bench, BenchOptions},
+    timings::{Timing, Timings},
+    try_read_file, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+#[cfg(feature = "dhat-heap")]
+compile_error!("the `registry` and `dhat-heap` features cannot be combined: every solution defines its own global allocator.");
+
+#[cfg(feature = "count-alloc")]
+compile_error!("the `registry` and `count-alloc` features cannot be combined: every solution defines its own global allocator.");
+
+/// A solution that can be run in-process.
+pub struct Solution {
+    pub day: Day,
+    /// Runs all parts of the solution against an input, returning `false` if a part failed or an answer is wrong.
+    pub run: fn(&str) -> bool,
+}
+
+include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+
+/// Look up the solution of a day.
+pub fn get(day: Day) -> Option<&'static Solution> {
+    SOLUTIONS.iter().find(|s| s.day == day)
+}
+
+/// Reports of the parts that ran in-process and were not taken by [`run_days`] yet.
+static REPORTS: Mutex<Vec<Report>> = Mutex::new(Vec::new());
+
+/// Collect the report of a part that ran in-process.
+pub(crate) fn collect(report: Report) {
+    REPORTS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .push(report);
+}
+
+/// Take the collected reports of a day.
+fn take_reports(day: Day) -> Vec<Report> {
+    let mut reports = REPORTS.lock().unwrap_or_else(PoisonError::into_inner);
+    let (taken, rest) = reports.drain(..).partition(|r| r.day == day);
+    *reports = rest;
+    taken
+}
+
+/// Run the solutions for a set of days in-process, then print the combined runtime of their parse steps and parts.
+/// If `bench` is set, solutions are benched with these options and timings are returned.
+///
+/// Days that exceed `timeout` are reported as failed. Threads can not be killed, so their solution
+/// keeps running in the background until the process exits. It would skew every later measurement,
+/// so the remaining days are reported as failed without running them and no total is printed.
+pub fn run_days(
+    days_to_run: &HashSet<Day>,
+    timeout: Option<Duration>,
+    bench: Option<&BenchOptions>,
+) -> MultiRun {
+    set_in_process_bench(bench.cloned());
+
+    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut all_reports: Vec<Report> = vec![];
+    let mut need_space = false;
+    let mut abandoned: Option<Day> = None;
+
+    for solution in SOLUTIONS.iter().filter(|s| days_to_run.contains(&s.day)) {
+        if need_space {
+            println!();
+        }
+        need_space = true;
+
+        let day = solution.day;
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        if let Some(timed_out) = abandoned {
+            let error = format!("skipped, day {timed_out} still runs in the background");
+            [1, 2].iter().for_each(|part| fail_part(day, *part, &error));
+        } else {
+            match try_read_file("inputs", day) {
+                Ok(input) => {
+                    if !run_day(solution, input, timeout) {
+                        abandoned = Some(day);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("{e}");
+                    [1, 2]
+                        .iter()
+                        .for_each(|part| fail_part(day, *part, e.reason()));
+                }
+            }
+        }
+
+        let reports = take_reports(day);
+
+        if reports.is_empty() {
+            println!("Not solved.");
+        } else {
+            timings.push(timing_from_reports(&reports, day));
+        }
+
+        all_reports.extend(reports);
+    }
+
+    if let Some(day) = abandoned {
+        println!("\n{ANSI_BOLD}Total (In-process):{ANSI_RESET} {ANSI_ITALIC}unavailable, day {day} timed out{ANSI_RESET}");
+        return MultiRun {
+            reports: all_reports,
+            timings: bench.map(|_| Timings { data: timings }),
+        };
+    }
+
+    let total_nanos: f64 = all_reports
+        .iter()
+        .filter(|r| r.status != Status::Failed)
+        .map(|r| r.duration_nanos)
+        .sum();
+    let total_millis = total_nanos / 1_000_000_f64;
+    println!(
+        "\n{ANSI_BOLD}Total (In-process):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+    );
+
+    MultiRun {
+        reports: all_reports,
+        timings: bench.map(|_| Timings { data: timings }),
+    }
+}
+
+/// Run a solution on its own thread, reporting the parts that did not finish within `timeout` as failed.
+/// Returns `false` if the thread timed out and was left running.
+fn run_day(solution: &Solution, input: String, timeout: Option<Duration>) -> bool {
+    let day = solution.day;
+    let run = solution.run;
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        let _ = sender.send(run(&input));
+    });
+
+    let outcome = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout).map(|_| ()),
+        None => receiver
+            .recv()
+            .map(|_| ())
+            .map_err(|_| RecvTimeoutError::Disconnected),
+    };
+
+    let error = match outcome {
+        Ok(()) => None,
+        Err(RecvTimeoutError::Timeout) => Some(format!(
+            "timed out after {:.1?}",
+            timeout.unwrap_or_default()
+        )),
+        Err(RecvTimeoutError::Disconnected) => Some("crashed".to_string()),
+    };
+
+    if let Some(error) = error {
+        let reports = REPORTS.lock().unwrap_or_else(PoisonError::into_inner);
+        let missing: Vec<u8> = [1, 2]
+            .into_iter()
+            .filter(|part| !reports.iter().any(|r| r.day == day && r.part == *part))
+            .collect();
+        drop(reports);
+
+        missing
+            .iter()
+            .for_each(|part| fail_part(day, *part, &error));
+    }
+
+    outcome != Err(RecvTimeoutError::Timeout)
+}
@@ -0,0 +1,310 @@
+/// Structured, machine-readable results that solution binaries emit for `run_multi`.
+///
+/// When a solution binary is invoked with `--report`, every part additionally prints one line
+/// that starts with [`REPORT_PREFIX`] followed by a JSON object. Parent processes consume these
+/// lines instead of scraping the human-readable output.
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{alloc, alloc::AllocStats, stats, stats::Stats, Day};
+
+/// Marks a stdout line as a report record. Starts with an ASCII record separator so it can
+/// never be confused with regular solution output.
+pub const REPORT_PREFIX: &str = "\x1eaoc-report ";
+
+/// Command-line flag that instructs a solution binary to emit report records.
+pub const REPORT_FLAG: &str = "--report";
+
+/// Outcome of running a single part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The part returned an answer.
+    Solved,
+    /// The part returned `None`.
+    Unsolved,
+    /// The part panicked, crashed or timed out.
+    Failed,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Status::Solved => "solved",
+            Status::Unsolved => "unsolved",
+            Status::Failed => "failed",
+        })
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(Status::Solved),
+            "unsolved" => Ok(Status::Unsolved),
+            "failed" => Ok(Status::Failed),
+            x => Err(format!("unknown report status `{x}`.")),
+        }
+    }
+}
+
+/// Result and timing of a single part of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    pub day: Day,
+    /// The part number, or `0` for the shared parse step of a solution.
+    pub part: u8,
+    pub answer: Option<String>,
+    /// Whether the answer matches the stored answer, `None` if no answer is stored.
+    pub correct: Option<bool>,
+    pub duration_nanos: f64,
+    pub samples: u128,
+    /// Benchmark statistics, present if the part was benched.
+    pub stats: Option<Stats>,
+    /// Heap usage of a single run, present if the solution was built with the `count-alloc` feature.
+    pub alloc: Option<AllocStats>,
+    pub status: Status,
+    /// Reason why the part failed.
+    pub error: Option<String>,
+}
+
+impl Report {
+    /// Create the report of a part that could not be run to completion.
+    pub fn failed(day: Day, part: u8, error: &str) -> Self {
+        Report {
+            day,
+            part,
+            answer: None,
+            correct: None,
+            duration_nanos: 0.0,
+            samples: 0,
+            stats: None,
+            alloc: None,
+            status: Status::Failed,
+            error: Some(error.to_string()),
+        }
+    }
+
+    /// Serialize the report to a single prefixed line.
+    pub fn to_line(&self) -> String {
+        let json = JsonValue::from(self)
+            .stringify()
+            .expect("report should be serializable");
+        format!("{REPORT_PREFIX}{json}")
+    }
+
+    /// Parse a report from a line of solution output.
+    /// Returns `None` if the line is not a report record.
+    pub fn from_line(line: &str) -> Option<Result<Self, String>> {
+        let json = line.strip_prefix(REPORT_PREFIX)?;
+        Some(
+            JsonValue::from_str(json)
+                .map_err(|_| "report is not valid JSON.".to_string())
+                .and_then(|value| Report::try_from(&value)),
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Report> for JsonValue {
+    fn from(value: &Report) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "correct".into(),
+            value.correct.map_or(JsonValue::Null, JsonValue::Boolean),
+        );
+        map.insert(
+            "duration_nanos".into(),
+            JsonValue::Number(value.duration_nanos),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "stats".into(),
+            stats::optional_to_json(value.stats.as_ref()),
+        );
+        map.insert(
+            "alloc".into(),
+            alloc::optional_to_json(value.alloc.as_ref()),
+        );
+        map.insert("status".into(), JsonValue::String(value.status.to_string()));
+        map.insert(
+            "error".into(),
+            value
+                .error
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Report {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected report.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .filter(|x| **x == 0.0 || **x == 1.0 || **x == 2.0)
+            .map(|x| *x as u8)
+            .ok_or("Expected report.part to be 0, 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.answer to be null or string.")?;
+
+        let correct = json
+            .get("correct")
+            .map(|v| if v.is_null() { None } else { v.get::<bool>() })
+            .ok_or("Expected report.correct to be null or boolean.")?;
+
+        let duration_nanos = json
+            .get("duration_nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected report.duration_nanos to be a number.")?;
+
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u128)
+            .ok_or("Expected report.samples to be a number.")?;
+
+        let stats = stats::optional_from_json(json, "stats")?;
+        let alloc = alloc::optional_from_json(json, "alloc")?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected report.status to be a string.")?
+            .parse()?;
+
+        let error = json
+            .get("error")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.error to be null or string.")?;
+
+        Ok(Report {
+            day,
+            part,
+            answer: answer.cloned(),
+            correct: correct.copied(),
+            duration_nanos,
+            samples,
+            stats,
+            alloc,
+            status,
+            error: error.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Report, Status, REPORT_PREFIX};
+    use crate::{
+        day,
+        template::{alloc::AllocStats, stats::Stats},
+    };
+
+    fn get_mock_report() -> Report {
+        Report {
+            day: day!(6),
+            part: 2,
+            answer: Some("1 (2 @ 3 samples)".into()),
+            correct: Some(false),
+            duration_nanos: 74_130.0,
+            samples: 100_000,
+            stats: Some(Stats {
+                samples: 100_000,
+                warmup: 10_000,
+                mean: 74_500.0,
+                median: 74_130.0,
+                min: 70_000.0,
+                max: 90_000.0,
+                stddev: 1_200.0,
+                ci_low: 74_492.5,
+                ci_high: 74_507.5,
+                outliers: 12,
+            }),
+            alloc: Some(AllocStats {
+                allocations: 3,
+                total_bytes: 2048,
+                peak_bytes: 1024,
+            }),
+            status: Status::Solved,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn roundtrips_reports() {
+        let report = get_mock_report();
+        let line = report.to_line();
+        assert!(line.starts_with(REPORT_PREFIX));
+        assert_eq!(Report::from_line(&line).unwrap().unwrap(), report);
+    }
+
+    #[test]
+    fn roundtrips_unsolved_reports() {
+        let report = Report {
+            answer: None,
+            correct: None,
+            stats: None,
+            alloc: None,
+            status: Status::Unsolved,
+            ..get_mock_report()
+        };
+        assert_eq!(
+            Report::from_line(&report.to_line()).unwrap().unwrap(),
+            report
+        );
+    }
+
+    #[test]
+    fn roundtrips_failed_reports() {
+        let report = Report::failed(day!(6), 1, "panicked: index out of bounds");
+        assert_eq!(
+            Report::from_line(&report.to_line()).unwrap().unwrap(),
+            report
+        );
+    }
+
+    #[test]
+    fn ignores_regular_output() {
+        assert!(Report::from_line("Part 1: 0 (74.13ns @ 100000 samples)").is_none());
+    }
+
+    #[test]
+    fn rejects_malformed_reports() {
+        let line = format!(r#"{REPORT_PREFIX}{{ "day": "01", "part": 3 }}"#);
+        assert!(Report::from_line(&line).unwrap().is_err());
+    }
+}
@@ -1,18 +1,117 @@
-use std::{collections::HashSet, io};
+use std::{collections::HashSet, io, path::Path, process, time::Duration};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{runner::BenchOptions, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
+    report::{Report, Status},
+    summary,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Options that control how solution binaries are invoked.
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    pub is_release: bool,
+    /// Build solutions with the `dhat` profile and heap profiler. Takes precedence over `is_release`.
+    pub is_dhat: bool,
+    /// Build solutions with the counting allocator to record their heap usage.
+    pub count_alloc: bool,
+    /// If set, solutions are benched with these options.
+    pub bench: Option<BenchOptions>,
+    /// If set, a solution is killed once it runs longer than this.
+    pub timeout: Option<Duration>,
+}
+
+/// Outcome of running the solutions for a set of days.
+pub struct MultiRun {
+    /// Reports of all parts that were run, in day order.
+    pub reports: Vec<Report>,
+    /// Benchmark timings, present if solutions were benched.
+    pub timings: Option<Timings>,
+}
+
+impl MultiRun {
+    /// Parts whose answer contradicts the answer stored in `data/answers.json`.
+    pub fn wrong_answers(&self) -> Vec<&Report> {
+        self.reports
+            .iter()
+            .filter(|r| r.correct == Some(false))
+            .collect()
+    }
+
+    /// Parts (or parse steps) that panicked, crashed or timed out.
+    pub fn failures(&self) -> Vec<&Report> {
+        self.reports
+            .iter()
+            .filter(|r| r.status == Status::Failed)
+            .collect()
+    }
+
+    /// Print an overview table of all days that were run.
+    pub fn print_summary(&self) {
+        summary::print(&self.reports);
+    }
+
+    /// Print failed parts and wrong answers, then exit with a non-zero status if there are any.
+    pub fn exit_on_failures(&self) {
+        let failures = self.failures();
+        let wrong_answers = self.wrong_answers();
+
+        if failures.is_empty() && wrong_answers.is_empty() {
+            return;
+        }
+
+        eprintln!();
+        for report in failures {
+            let part = if report.part == 0 {
+                "parse".to_string()
+            } else {
+                format!("part {}", report.part)
+            };
+            eprintln!(
+                "✖ Day {} {part}: {}",
+                report.day,
+                report.error.as_deref().unwrap_or("unknown error")
+            );
+        }
+        for report in wrong_answers {
+            eprintln!(
+                "✗ Day {} part {} does not match the stored answer.",
+                report.day, report.part
+            );
+        }
+        process::exit(1);
+    }
+}
+
+/// Run the solutions for a set of days. If `options.bench` is set, solutions are benched and timings are returned.
+pub fn run_multi(days_to_run: &HashSet<Day>, options: &RunOptions) -> MultiRun {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut all_reports: Vec<Report> = vec![];
+
+    // NOTE: use non-duplicate, sorted day values.
+    // days that have not been scaffolded yet have no binary to build.
+    let days: Vec<Day> = all_days()
+        .filter(|day| days_to_run.contains(day) && Path::new(&get_path_for_bin(*day)).exists())
+        .collect();
+
+    let build = child_commands::build_solutions(&days, options).unwrap_or_else(|e| {
+        // cargo could not be invoked at all, every day fails to build.
+        child_commands::Build {
+            shared_errors: vec![format!("could not run cargo build: {e:?}")],
+            ..Default::default()
+        }
+    });
+
+    if !build.shared_errors.is_empty() {
+        eprintln!("{ANSI_BOLD}Build errors{ANSI_RESET}");
+        build.shared_errors.iter().for_each(|e| eprintln!("{e}"));
+        println!();
+    }
 
     let mut need_space = false;
 
-    // NOTE: use non-duplicate, sorted day values.
     all_days()
         .filter(|day| days_to_run.contains(day))
         .for_each(|day| {
@@ -24,26 +123,53 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let reports = if !days.contains(&day) {
+                vec![]
+            } else if let Some(executable) = build.executables.get(&day) {
+                child_commands::run_solution(day, executable, options).unwrap_or_else(|e| {
+                    // the binary could not be invoked at all, treat this as a failure of the whole day.
+                    fail_day(day, &format!("could not run solution: {e:?}"))
+                })
+            } else {
+                if let Some(errors) = build.errors.get(&day) {
+                    errors.iter().for_each(|e| eprintln!("{e}"));
+                }
+                fail_day(day, "failed to build")
+            };
 
-            if output.is_empty() {
+            if reports.is_empty() {
                 println!("Not solved.");
             } else {
-                let val = child_commands::parse_exec_time(&output, day);
+                let val = child_commands::timing_from_reports(&reports, day);
                 timings.push(val);
+                all_reports.extend(reports);
             }
         });
 
-    if is_timed {
-        let timings = Timings { data: timings };
+    let run = MultiRun {
+        reports: all_reports,
+        timings: options.bench.as_ref().map(|_| Timings { data: timings }),
+    };
+
+    if let Some(timings) = &run.timings {
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
-    } else {
-        None
     }
+
+    run
+}
+
+/// Report both parts of a day as failed.
+fn fail_day(day: Day, error: &str) -> Vec<Report> {
+    [1, 2]
+        .into_iter()
+        .map(|part| {
+            println!("Part {part}: ✖ {error}");
+            Report::failed(day, part, error)
+        })
+        .collect()
 }
 
 #[allow(dead_code)]
@@ -65,39 +191,77 @@ pub fn get_path_for_bin(day: Day) -> String {
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting the reports they emit.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{Error, RunOptions};
+    use crate::template::{
+        report::{Report, Status, REPORT_FLAG},
+        timings::PartTiming,
+        Day,
+    };
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        collections::HashMap,
+        io::{BufRead, BufReader, Read},
+        path::{Path, PathBuf},
+        process::{Child, Command, ExitStatus, Stdio},
+        str::FromStr,
         thread,
+        time::{Duration, Instant},
     };
+    use tinyjson::JsonValue;
+
+    /// Interval in which a running child is checked for exit or timeout.
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+    /// Regular exit code of a solution that ran to completion with failing parts or wrong answers.
+    const SOLUTION_FAILURE_CODE: i32 = 1;
+
+    /// Outcome of building the solution binaries.
+    #[derive(Debug, Default, PartialEq, Eq)]
+    pub struct Build {
+        /// Paths of the built executables, by day.
+        pub executables: HashMap<Day, PathBuf>,
+        /// Compiler errors of solutions that failed to build, by day.
+        pub errors: HashMap<Day, Vec<String>>,
+        /// Build errors that can not be attributed to a solution, e.g. errors in the library.
+        pub shared_errors: Vec<String>,
+    }
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    /// Build the solution bins for a set of days with a single cargo invocation.
+    ///
+    /// Compiler output is captured instead of forwarded, so that errors can be reported per day.
+    /// Bins are passed explicitly instead of using `--bins`, which would also rebuild the running CLI.
+    pub fn build_solutions(days: &[Day], options: &RunOptions) -> Result<Build, Error> {
+        if days.is_empty() {
+            return Ok(Build::default());
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let mut args: Vec<String> = vec![
+            "build".into(),
+            "--quiet".into(),
+            "--keep-going".into(),
+            "--message-format=json".into(),
+        ];
 
-        if is_release {
-            args.push("--release");
+        for day in days {
+            args.push("--bin".into());
+            args.push(day.to_string());
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        if options.is_dhat {
+            args.extend([
+                "--profile".into(),
+                "dhat".into(),
+                "--features".into(),
+                "dhat-heap".into(),
+            ]);
+        } else if options.is_release {
+            args.push("--release".into());
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        if options.count_alloc {
+            args.extend(["--features".into(), "count-alloc".into()]);
+        }
 
         let mut cmd = Command::new("cargo")
             .args(&args)
@@ -105,90 +269,222 @@ pub mod child_commands {
             .stderr(Stdio::piped())
             .spawn()?;
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        let mut stderr = cmd.stderr.take().ok_or(Error::BrokenPipe)?;
+        let stderr_thread = thread::spawn(move || {
+            let mut output = String::new();
+            let _ = stderr.read_to_string(&mut output);
+            output
+        });
+
+        let stdout = BufReader::new(cmd.stdout.take().ok_or(Error::BrokenPipe)?);
+        let mut build = parse_build_output(stdout.lines().map_while(Result::ok));
+
+        let status = cmd.wait()?;
+        let stderr = stderr_thread.join().unwrap_or_default();
+
+        // surface cargo's own errors (e.g. an invalid manifest) if no compiler error explains the failure.
+        if !status.success() && build.errors.is_empty() && build.shared_errors.is_empty() {
+            build.shared_errors.push(stderr.trim_end().to_string());
+        }
+
+        Ok(build)
+    }
+
+    /// Collect built executables and compiler errors from cargo's JSON messages.
+    pub fn parse_build_output(lines: impl Iterator<Item = String>) -> Build {
+        let mut build = Build::default();
+
+        for line in lines {
+            let Ok(json) = JsonValue::from_str(&line) else {
+                continue;
+            };
+
+            let Some(message) = json.get::<HashMap<String, JsonValue>>() else {
+                continue;
+            };
+
+            let string = |value: Option<&JsonValue>| value.and_then(|v| v.get::<String>()).cloned();
+
+            let target = message
+                .get("target")
+                .and_then(|t| t.get::<HashMap<String, JsonValue>>())
+                .and_then(|t| string(t.get("name")));
+
+            let day = target.as_deref().and_then(|name| Day::from_str(name).ok());
+
+            match string(message.get("reason")).as_deref() {
+                Some("compiler-artifact") => {
+                    if let (Some(day), Some(executable)) = (day, string(message.get("executable")))
+                    {
+                        build.executables.insert(day, PathBuf::from(executable));
+                    }
+                }
+                Some("compiler-message") => {
+                    let Some(diagnostic) = message
+                        .get("message")
+                        .and_then(|m| m.get::<HashMap<String, JsonValue>>())
+                    else {
+                        continue;
+                    };
+
+                    if string(diagnostic.get("level")).as_deref() != Some("error") {
+                        continue;
+                    }
+
+                    let Some(rendered) = string(diagnostic.get("rendered")) else {
+                        continue;
+                    };
+
+                    let rendered = rendered.trim_end().to_string();
+
+                    match day {
+                        Some(day) => build.errors.entry(day).or_default().push(rendered),
+                        None => build.shared_errors.push(rendered),
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        build
+    }
+
+    /// Run the built solution bin for a given day, returning the reports emitted for each part.
+    ///
+    /// If the solution crashes or times out, parts that did not report are reported as failed.
+    pub fn run_solution(
+        day: Day,
+        executable: &Path,
+        options: &RunOptions,
+    ) -> Result<Vec<Report>, Error> {
+        // request structured reports from the child.
+        let mut args: Vec<String> = vec![REPORT_FLAG.into()];
+
+        if let Some(bench) = &options.bench {
+            // mirror `--time` flag and bench options to child invocations.
+            args.push("--time".into());
+            args.extend(bench.to_args());
+        }
+
+        // spawn child command with piped stdout/stderr.
+        // forward output to stdout/stderr while grabbing report lines.
+
+        let mut cmd = Command::new(executable)
+            .args(&args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
 
-        let mut output = vec![];
+        let stdout = BufReader::new(cmd.stdout.take().ok_or(Error::BrokenPipe)?);
+        let stderr = BufReader::new(cmd.stderr.take().ok_or(Error::BrokenPipe)?);
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+        let stderr_thread = thread::spawn(move || {
+            stderr.lines().map_while(Result::ok).for_each(|line| {
+                eprintln!("{line}");
             });
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        let stdout_thread = thread::spawn(move || {
+            let mut reports = vec![];
+
+            for line in stdout.lines().map_while(Result::ok) {
+                match Report::from_line(&line) {
+                    Some(Ok(report)) => reports.push(report),
+                    Some(Err(e)) => eprintln!("Could not parse report: {e}"),
+                    None => println!("{line}"),
+                }
+            }
+
+            reports
+        });
+
+        let status = wait_with_timeout(&mut cmd, options.timeout)?;
+
+        let mut reports = stdout_thread.join().unwrap_or_default();
+        let _ = stderr_thread.join();
+
+        let error = match status {
+            None => Some(format!(
+                "timed out after {:.1?}",
+                options.timeout.unwrap_or_default()
+            )),
+            Some(status) if !status.success() && status.code() != Some(SOLUTION_FAILURE_CODE) => {
+                Some(format!("crashed ({status})"))
+            }
+            _ => None,
+        };
+
+        if let Some(error) = error {
+            for part in [1, 2] {
+                if !reports.iter().any(|r| r.part == part) {
+                    println!("Part {part}: ✖ {error}");
+                    reports.push(Report::failed(day, part, &error));
+                }
+            }
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        Ok(reports)
+    }
+
+    /// Wait for a child to exit. Kills the child and returns `None` if it exceeds `timeout`.
+    fn wait_with_timeout(
+        cmd: &mut Child,
+        timeout: Option<Duration>,
+    ) -> Result<Option<ExitStatus>, Error> {
+        let Some(timeout) = timeout else {
+            return Ok(Some(cmd.wait()?));
+        };
+
+        let timer = Instant::now();
 
-        Ok(output)
+        loop {
+            if let Some(status) = cmd.try_wait()? {
+                return Ok(Some(status));
+            }
+
+            if timer.elapsed() >= timeout {
+                cmd.kill()?;
+                cmd.wait()?;
+                return Ok(None);
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        }
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
+    /// Collect the benchmark timings of a day from the reports of its parse step and parts.
+    /// Only benched reports (i.e. reports with more than one sample) are taken into account.
+    pub fn timing_from_reports(reports: &[Report], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
+            parse: None,
             part_1: None,
             part_2: None,
-            total_nanos: 0_f64,
+            source_hash: None,
         };
 
-        output
+        reports
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+            .filter(|r| r.day == day && r.status == Status::Solved && r.samples > 1)
+            .for_each(|r| {
+                let timing = Some(PartTiming {
+                    nanos: r.duration_nanos,
+                    samples: r.samples,
+                    stats: r.stats.clone(),
+                    alloc: r.alloc,
+                });
+
+                match r.part {
+                    0 => timings.parse = timing,
+                    1 => timings.part_1 = timing,
+                    2 => timings.part_2 = timing,
+                    _ => {}
                 }
-
-                timings.total_nanos += nanos;
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -205,53 +501,136 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_build_output, timing_from_reports};
+        use std::path::PathBuf;
+
+        use crate::{
+            day,
+            template::report::{Report, Status},
+        };
 
-        use crate::day;
+        fn report(part: u8, answer: Option<&str>, duration_nanos: f64, samples: u128) -> Report {
+            Report {
+                day: day!(1),
+                part,
+                answer: answer.map(Into::into),
+                correct: None,
+                duration_nanos,
+                samples,
+                stats: None,
+                alloc: None,
+                status: if answer.is_some() {
+                    Status::Solved
+                } else {
+                    Status::Unsolved
+                },
+                error: None,
+            }
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
+        fn collects_execution_times() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    report(1, Some("0"), 74.13, 100_000),
+                    report(2, Some("10"), 74_130_000.0, 99_999),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos(), 74130074.13_f64);
+            assert_eq!(res.part_1.as_ref().unwrap().to_string(), "74.13ns");
+            assert_eq!(res.part_1.unwrap().samples, 100_000);
+            assert_eq!(res.part_2.unwrap().to_string(), "74.13ms");
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+        fn collects_with_patterns_in_answer() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    report(1, Some("@ @ @ ( ) ms (1s @ 5 samples)"), 2e9, 5),
+                    report(2, Some("10s"), 1e8, 10),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_approx_eq!(res.total_nanos(), 2100000000_f64);
+            assert_eq!(res.part_1.unwrap().to_string(), "2.0s");
+            assert_eq!(res.part_2.unwrap().to_string(), "100.0ms");
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
+        fn collects_missing_parts() {
+            let res = timing_from_reports(
+                &[report(1, None, 10.0, 1), report(2, None, 10.0, 1)],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos(), 0_f64);
+            assert_eq!(res.part_1.is_none(), true);
+            assert_eq!(res.part_2.is_none(), true);
+        }
+
+        #[test]
+        fn collects_parse_times() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    Report {
+                        answer: None,
+                        ..report(0, Some(""), 1e6, 10)
+                    },
+                    report(1, Some("1"), 2e6, 10),
+                    report(2, Some("2"), 3e6, 10),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
+            assert_approx_eq!(res.total_nanos(), 6e6_f64);
+            assert_eq!(res.parse.unwrap().nanos, 1e6);
+            assert_eq!(res.part_1.unwrap().nanos, 2e6);
+            assert_eq!(res.part_2.unwrap().nanos, 3e6);
+        }
+
+        #[test]
+        fn ignores_unbenched_parts() {
+            let res = timing_from_reports(&[report(1, Some("1"), 10.0, 1)], day!(1));
+            assert_approx_eq!(res.total_nanos(), 0_f64);
             assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+        }
+
+        #[test]
+        fn parses_build_output() {
+            let lines = [
+                r#"{"reason":"compiler-artifact","target":{"name":"advent_of_code","kind":["lib"]},"executable":null}"#,
+                r#"{"reason":"compiler-message","target":{"name":"01","kind":["bin"]},"message":{"level":"warning","rendered":"warning: unused variable"}}"#,
+                r#"{"reason":"compiler-artifact","target":{"name":"01","kind":["bin"]},"executable":"/target/release/01"}"#,
+                r#"{"reason":"compiler-message","target":{"name":"02","kind":["bin"]},"message":{"level":"error","rendered":"error: expected `;`\n"}}"#,
+                r#"{"reason":"build-finished","success":false}"#,
+                "not json",
+            ];
+
+            let build = parse_build_output(lines.into_iter().map(Into::into));
+
+            assert_eq!(build.executables.len(), 1);
+            assert_eq!(
+                build.executables.get(&day!(1)),
+                Some(&PathBuf::from("/target/release/01"))
+            );
+            assert_eq!(build.errors.len(), 1);
+            assert_eq!(
+                build.errors.get(&day!(2)).unwrap(),
+                &vec!["error: expected `;`".to_string()]
+            );
+            assert!(build.shared_errors.is_empty());
+        }
+
+        #[test]
+        fn attributes_library_errors_to_all_days() {
+            let lines = [
+                r#"{"reason":"compiler-message","target":{"name":"advent_of_code","kind":["lib"]},"message":{"level":"error","rendered":"error[E0425]: cannot find value"}}"#,
+            ];
+
+            let build = parse_build_output(lines.into_iter().map(Into::into));
+
+            assert!(build.executables.is_empty());
+            assert!(build.errors.is_empty());
+            assert_eq!(build.shared_errors, vec!["error[E0425]: cannot find value"]);
         }
     }
 }
@@ -1,63 +1,390 @@
 /// Encapsulates code that interacts with solution functions.
+use std::any::Any;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
-use std::time::{Duration, Instant};
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+#[cfg(feature = "registry")]
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use std::{cmp, env, process};
 
+use crate::template::alloc::{self, format_bytes, AllocStats};
+use crate::template::answers::{Answers, Check};
+use crate::template::aoc_cli::AocCommandError;
+use crate::template::aoc_client::{self, Client};
+use crate::template::report::{Report, Status, REPORT_FLAG};
+use crate::template::stats::Stats;
+use crate::template::submissions::{format_wait, parse_response, Submission, Submissions, Verdict};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, Day, InputSource, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+const BUDGET_FLAG: &str = "--budget";
+const MIN_SAMPLES_FLAG: &str = "--min-samples";
+const MAX_SAMPLES_FLAG: &str = "--max-samples";
+const WARMUP_FLAG: &str = "--warmup";
+
+/// Controls how long and how often a part is sampled when benching.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BenchOptions {
+    /// Approximate time spent sampling a single part.
+    pub budget: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+    /// Number of discarded iterations before sampling. Defaults to 10% of the samples if unset.
+    pub warmup: Option<u128>,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            budget: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10000,
+            warmup: None,
+        }
+    }
+}
+
+impl BenchOptions {
+    /// Build options from optional command-line values, falling back to defaults.
+    pub fn new(
+        budget_millis: Option<u64>,
+        min_samples: Option<u128>,
+        max_samples: Option<u128>,
+        warmup: Option<u128>,
+    ) -> Result<Self, String> {
+        let defaults = Self::default();
+
+        let options = Self {
+            budget: budget_millis.map_or(defaults.budget, Duration::from_millis),
+            min_samples: min_samples.unwrap_or(defaults.min_samples),
+            max_samples: max_samples.unwrap_or(defaults.max_samples),
+            warmup,
+        };
+
+        if options.min_samples == 0 {
+            return Err("--min-samples must be at least 1.".into());
+        }
+
+        if options.min_samples > options.max_samples {
+            return Err("--min-samples must not be greater than --max-samples.".into());
+        }
+
+        Ok(options)
+    }
+
+    /// Command-line arguments that mirror these options to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![
+            BUDGET_FLAG.into(),
+            self.budget.as_millis().to_string(),
+            MIN_SAMPLES_FLAG.into(),
+            self.min_samples.to_string(),
+            MAX_SAMPLES_FLAG.into(),
+            self.max_samples.to_string(),
+        ];
+
+        if let Some(warmup) = self.warmup {
+            args.push(WARMUP_FLAG.into());
+            args.push(warmup.to_string());
+        }
+
+        args
+    }
+
+    /// Read the options passed to the current solution binary.
+    fn from_env() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        let options = Self::new(
+            arg_value(&args, BUDGET_FLAG),
+            arg_value(&args, MIN_SAMPLES_FLAG),
+            arg_value(&args, MAX_SAMPLES_FLAG),
+            arg_value(&args, WARMUP_FLAG),
+        );
+
+        options.unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        })
+    }
+
+    fn sample_count(&self, base_time: &Duration) -> u128 {
+        (self.budget.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+            .clamp(self.min_samples, self.max_samples)
+    }
+
+    fn warmup_count(&self, samples: u128) -> u128 {
+        self.warmup.unwrap_or_else(|| cmp::max(samples / 10, 1))
+    }
+}
+
+/// Bench options of the solutions that run in-process. Their command-line arguments are the ones of
+/// the command that runs them, so the registry hands the options over here instead.
+#[cfg(feature = "registry")]
+static IN_PROCESS_BENCH: Mutex<Option<BenchOptions>> = Mutex::new(None);
+
+/// Bench the solutions that run in-process with `options`, or run them once if `None`.
+#[cfg(feature = "registry")]
+pub(crate) fn set_in_process_bench(options: Option<BenchOptions>) {
+    *IN_PROCESS_BENCH
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner) = options;
+}
+
+/// Options to bench a part with, or `None` if it should only run once.
+fn bench_options() -> Option<BenchOptions> {
+    #[cfg(feature = "registry")]
+    if let Some(options) = IN_PROCESS_BENCH
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+    {
+        return Some(options);
+    }
+
+    env::args()
+        .any(|x| x == "--time")
+        .then(BenchOptions::from_env)
+}
+
+/// Read the value following `flag` from the command-line arguments.
+fn arg_value<T: FromStr>(args: &[String], flag: &str) -> Option<T> {
+    let index = args.iter().position(|x| x == flag)?;
+
+    let Some(value) = args.get(index + 1).and_then(|x| x.parse().ok()) else {
+        eprintln!("Unexpected command-line input. Expected a number after {flag}.");
+        process::exit(1);
+    };
+
+    Some(value)
+}
+
+/// Run, time and report the shared parse step of a solution, returning the parsed input.
+/// Returns `None` if the parse step panicked.
+pub fn run_parse<P>(func: impl Fn(&str) -> P, input: &str, day: Day) -> Option<P> {
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+        run_timed(&func, input, |_| print!("Parse:"))
+    }));
+
+    let (parsed, duration, samples, stats, alloc) = match outcome {
+        Ok(x) => x,
+        Err(payload) => {
+            fail_part(day, 0, &format!("panicked: {}", panic_message(&*payload)));
+            return None;
+        }
+    };
+
+    print!("\r");
+    println!("Parse:{}", format_duration(&duration, samples));
+
+    if let Some(stats) = &stats {
+        print_stats(stats);
+    }
+
+    if let Some(alloc) = &alloc {
+        print_alloc(alloc);
+    }
+
+    emit_report(Report {
+        day,
+        part: 0,
+        answer: None,
+        correct: None,
+        #[allow(clippy::cast_precision_loss)]
+        duration_nanos: duration.as_nanos() as f64,
+        samples,
+        stats,
+        alloc,
+        status: Status::Solved,
+        error: None,
+    });
+
+    Some(parsed)
+}
+
+/// Run, time and report a single part of a solution.
+///
+/// Returns `false` if the part panicked or its answer contradicts the answer stored in `data/answers.json`.
+pub fn run_part<I: Copy, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: u8,
+) -> bool {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+        run_timed(&func, input, |result| print_result(result, &part_str, ""))
+    }));
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let (result, duration, samples, stats, alloc) = match outcome {
+        Ok(x) => x,
+        Err(payload) => {
+            fail_part(
+                day,
+                part,
+                &format!("panicked: {}", panic_message(&*payload)),
+            );
+            return false;
+        }
+    };
+
+    let answer = result.as_ref().map(ToString::to_string);
+
+    // answers are only stored for the puzzle input and named inputs.
+    let source = InputSource::from_env();
+    let stored_answer = answer.as_deref().filter(|_| source.has_stored_answers());
+
+    let recorded = stored_answer.and_then(|answer| record_answer(answer, day, source.name(), part));
+
+    let check = stored_answer.map_or(Check::Unknown, |answer| match Answers::read_from_file() {
+        Ok(answers) => answers.check(day, source.name(), part, answer),
+        Err(e) => {
+            eprintln!("Could not check answer for part {part}: {e}");
+            Check::Unknown
+        }
+    });
+
+    print_result(
+        &result,
+        &part_str,
+        &format!(
+            "{}{}",
+            format_duration(&duration, samples),
+            format_check(&check)
+        ),
+    );
+
+    if let Some(stats) = &stats {
+        print_stats(stats);
+    }
+
+    if let Some(alloc) = &alloc {
+        print_alloc(alloc);
+    }
+
+    match recorded {
+        Some(Ok(())) => println!("Recorded answer for part {part}."),
+        Some(Err(e)) => eprintln!("Failed to record answer for part {part}: {e}"),
+        None => {}
+    }
+
+    emit_report(Report {
+        day,
+        part,
+        status: if answer.is_some() {
+            Status::Solved
+        } else {
+            Status::Unsolved
+        },
+        answer,
+        correct: match check {
+            Check::Correct => Some(true),
+            Check::Wrong { .. } => Some(false),
+            Check::Unknown => None,
+        },
+        #[allow(clippy::cast_precision_loss)]
+        duration_nanos: duration.as_nanos() as f64,
+        samples,
+        stats,
+        alloc,
+        error: None,
+    });
 
     if let Some(result) = result {
         submit_result(result, day, part);
     }
+
+    !matches!(check, Check::Wrong { .. })
+}
+
+/// Print and report a part (or the parse step, as part `0`) that could not be run to completion.
+pub fn fail_part(day: Day, part: u8, error: &str) {
+    let label = if part == 0 {
+        "Parse".to_string()
+    } else {
+        format!("Part {part}")
+    };
+
+    print!("\r");
+    println!("{label}: ✖ {error}");
+
+    emit_report(Report::failed(day, part, error));
+}
+
+/// Hand the report of a part to the caller: as a report line if `--report` was passed, and to the
+/// in-process registry if it is compiled in.
+fn emit_report(report: Report) {
+    if env::args().any(|x| x == REPORT_FLAG) {
+        println!("{}", report.to_line());
+    }
+
+    #[cfg(feature = "registry")]
+    crate::template::registry::collect(report);
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic payload".into())
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. in release, the function is warmed up and benched (approx. 1 second of execution time or 10 samples, whatever take longer.
+///     These bounds can be configured via `--budget`, `--min-samples`, `--max-samples` and `--warmup`.)
+///     The reported duration is the median of all samples.
+///
+/// Heap usage is measured for the first execution only, if the `count-alloc` feature is enabled.
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, u128, Option<Stats>, Option<AllocStats>) {
     let timer = Instant::now();
-    let result = {
+    let (result, alloc) = {
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        alloc::measure(|| func(input))
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    if let Some(options) = bench_options() {
+        let stats = bench(func, input, &base_time, &options);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let median = Duration::from_nanos(stats.median as u64);
+        (result, median, stats.samples, Some(stats), alloc)
     } else {
-        (base_time, 1)
-    };
-
-    (result, run.0, run.1)
+        (result, base_time, 1, None, alloc)
+    }
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+/// Bench a solution part: run a number of warmup iterations that are discarded, then sample.
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    options: &BenchOptions,
+) -> Stats {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let bench_iterations = options.sample_count(base_time);
+    let warmup_iterations = options.warmup_count(bench_iterations);
+
+    for _ in 0..warmup_iterations {
+        black_box(func(black_box(input)));
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -67,19 +394,7 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
-
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    Stats::from_samples(&timers, warmup_iterations).expect("bench should take at least one sample")
 }
 
 fn format_duration(duration: &Duration, samples: u128) -> String {
@@ -90,6 +405,39 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn print_stats(stats: &Stats) {
+    let nanos = |x: f64| Duration::from_nanos(x.max(0.0) as u64);
+
+    println!(
+        "        {ANSI_ITALIC}min {:.1?} · max {:.1?} · σ {:.1?} · 95% CI [{:.1?}, {:.1?}] · {} outliers · {} warmup{ANSI_RESET}",
+        nanos(stats.min),
+        nanos(stats.max),
+        nanos(stats.stddev),
+        nanos(stats.ci_low),
+        nanos(stats.ci_high),
+        stats.outliers,
+        stats.warmup,
+    );
+}
+
+fn print_alloc(alloc: &AllocStats) {
+    println!(
+        "        {ANSI_ITALIC}{} allocations · {} total · {} peak{ANSI_RESET}",
+        alloc.allocations,
+        format_bytes(alloc.total_bytes),
+        format_bytes(alloc.peak_bytes),
+    );
+}
+
+fn format_check(check: &Check) -> String {
+    match check {
+        Check::Correct => " ✓".into(),
+        Check::Wrong { expected } => format!(" ✗ (expected {expected})"),
+        Check::Unknown => String::new(),
+    }
+}
+
 fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
@@ -125,20 +473,36 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
+/// Store the answer of a part in `data/answers.json` if `--record <part>` was passed for this part.
+fn record_answer(
+    answer: &str,
     day: Day,
+    input: Option<&str>,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<(), String>> {
     let args: Vec<String> = env::args().collect();
 
-    if !args.contains(&"--submit".into()) {
+    if arg_value::<u8>(&args, "--record")? != part {
         return None;
     }
 
+    Some(Answers::read_from_file().and_then(|mut answers| {
+        answers.set(day, input, part, answer);
+        answers.store_file().map_err(|e| e.to_string())
+    }))
+}
+
+/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
+///  1. we are in `--release` mode.
+///  2. aoc-cli is installed, or the native client is selected.
+///  3. previous submissions do not rule out the answer.
+fn submit_result<T: Display>(result: T, day: Day, part: u8) {
+    let args: Vec<String> = env::args().collect();
+
+    if !args.contains(&"--submit".into()) {
+        return;
+    }
+
     if args.len() < 3 {
         eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
         process::exit(1);
@@ -152,7 +516,78 @@ fn submit_result<T: Display>(
     };
 
     if part_submit != part {
-        return None;
+        return;
+    }
+
+    let answer = result.to_string();
+    let mut submissions = match Submissions::read_from_file() {
+        Ok(submissions) => submissions,
+        Err(e) => {
+            eprintln!("Not submitting {answer}: {e}");
+            process::exit(1);
+        }
+    };
+
+    if let Err(rejection) = submissions.check_candidate(day, part, &answer, unix_now()) {
+        eprintln!("Not submitting {answer}: {rejection}");
+        process::exit(1);
+    }
+
+    let (verdict, cooldown) = parse_response(&send_submission(day, part, &answer));
+
+    submissions.push(
+        day,
+        Submission {
+            timestamp: unix_now(),
+            part,
+            answer: answer.clone(),
+            verdict,
+            cooldown,
+        },
+    );
+
+    if let Err(e) = submissions.store_file() {
+        eprintln!("failed to store submission: {e}");
+    }
+
+    match verdict {
+        Verdict::Correct => match Answers::read_from_file() {
+            Ok(mut answers) => {
+                answers.set(day, None, part, &answer);
+                if let Err(e) = answers.store_file() {
+                    eprintln!("failed to store answer: {e}");
+                }
+            }
+            Err(e) => eprintln!("failed to store answer: {e}"),
+        },
+        Verdict::RateLimited => {
+            let wait = cooldown.map_or("a moment".into(), format_wait);
+            eprintln!("Answer was not checked because an answer was submitted too recently. Try again in {wait}.");
+        }
+        Verdict::Unknown => {
+            eprintln!("Could not parse the verdict of the submission, it was recorded as unknown.");
+        }
+        _ => {}
+    }
+}
+
+/// Submit an answer with the selected client and return the response.
+fn send_submission(day: Day, part: u8, answer: &str) -> String {
+    if aoc_client::is_selected() {
+        println!("Submitting result...");
+
+        let response = Client::from_env().and_then(|client| client.submit(day, part, answer));
+
+        return match response {
+            Ok(response) => {
+                println!("{response}");
+                response
+            }
+            Err(e) => {
+                eprintln!("failed to submit: {e}");
+                process::exit(1);
+            }
+        };
     }
 
     if aoc_cli::check().is_err() {
@@ -161,5 +596,69 @@ fn submit_result<T: Display>(
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+
+    match aoc_cli::submit(day, part, answer) {
+        Ok(output) | Err(AocCommandError::BadExitStatus(output)) => {
+            String::from_utf8_lossy(&output.stdout).into_owned()
+        }
+        Err(e) => {
+            eprintln!("failed to call aoc-cli: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |x| x.as_secs())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::BenchOptions;
+    use std::time::Duration;
+
+    #[test]
+    fn uses_defaults() {
+        let options = BenchOptions::new(None, None, None, None).unwrap();
+        assert_eq!(options, BenchOptions::default());
+        assert_eq!(options.sample_count(&Duration::from_millis(570)), 10);
+        assert_eq!(options.sample_count(&Duration::from_nanos(50)), 10000);
+        assert_eq!(options.warmup_count(10000), 1000);
+        assert_eq!(options.warmup_count(5), 1);
+    }
+
+    #[test]
+    fn respects_budget_and_bounds() {
+        let options = BenchOptions::new(Some(100), Some(2), Some(50), Some(0)).unwrap();
+        assert_eq!(options.sample_count(&Duration::from_millis(570)), 2);
+        assert_eq!(options.sample_count(&Duration::from_millis(10)), 10);
+        assert_eq!(options.sample_count(&Duration::from_nanos(50)), 50);
+        assert_eq!(options.warmup_count(50), 0);
+    }
+
+    #[test]
+    fn rejects_invalid_bounds() {
+        assert!(BenchOptions::new(None, Some(0), None, None).is_err());
+        assert!(BenchOptions::new(None, Some(100), Some(10), None).is_err());
+    }
+
+    #[test]
+    fn mirrors_options_to_args() {
+        let options = BenchOptions::new(Some(250), Some(5), Some(500), Some(3)).unwrap();
+        assert_eq!(
+            options.to_args(),
+            [
+                "--budget",
+                "250",
+                "--min-samples",
+                "5",
+                "--max-samples",
+                "500",
+                "--warmup",
+                "3"
+            ]
+        );
+    }
 }
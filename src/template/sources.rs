@@ -0,0 +1,118 @@
+/// Content hashes of solution sources, used to tell which stored timings are outdated.
+use std::{collections::BTreeSet, fs, path::Path};
+
+use regex::Regex;
+
+use crate::template::{checksums::checksum, readme_benchmarks::get_path_for_bin, Day};
+
+/// Items of the library root that belong to the template rather than to shared solution code.
+const TEMPLATE_ITEMS: [&str; 3] = ["template", "solution", "day"];
+
+/// Hash of the source of a day together with the shared library code it refers to.
+/// Returns `None` if the day has no solution.
+pub fn source_hash(day: Day) -> Option<String> {
+    let path = get_path_for_bin(day);
+    let source = fs::read_to_string(&path).ok()?;
+
+    let mut files = BTreeSet::from([path]);
+    for item in library_items(&source) {
+        files.extend(library_files(&item));
+    }
+
+    let mut content = vec![];
+    for file in files {
+        content.extend(file.as_bytes());
+        content.push(0);
+        content.extend(fs::read(&file).unwrap_or_default());
+        content.push(0);
+    }
+
+    Some(checksum(&content))
+}
+
+/// Names of the library items a solution refers to via `advent_of_code::<item>`, excluding the template.
+fn library_items(source: &str) -> BTreeSet<String> {
+    let re = Regex::new(r"advent_of_code::(?:\{([^}]*)\}|(\w+))").unwrap();
+
+    re.captures_iter(source)
+        .flat_map(|caps| match (caps.get(1), caps.get(2)) {
+            (Some(group), _) => group
+                .as_str()
+                .split(',')
+                .filter_map(|x| x.trim().split("::").next())
+                .map(|x| x.trim_start_matches('{').trim().to_string())
+                .collect(),
+            (None, Some(item)) => vec![item.as_str().to_string()],
+            (None, None) => vec![],
+        })
+        .filter(|x| !x.is_empty() && !TEMPLATE_ITEMS.contains(&x.as_str()))
+        .collect()
+}
+
+/// Files that define a library item: the module file or directory, or `src/lib.rs` for items declared there.
+fn library_files(item: &str) -> Vec<String> {
+    let file = format!("./src/{item}.rs");
+    let dir = format!("./src/{item}");
+
+    if Path::new(&file).is_file() {
+        vec![file]
+    } else if Path::new(&dir).is_dir() {
+        let mut files = vec![];
+        collect_rust_files(Path::new(&dir), &mut files);
+        files
+    } else {
+        vec!["./src/lib.rs".into()]
+    }
+}
+
+fn collect_rust_files(dir: &Path, files: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_rust_files(&path, files);
+        } else if path.extension().is_some_and(|x| x == "rs") {
+            files.push(path.to_string_lossy().to_string());
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{library_files, library_items, source_hash};
+    use crate::day;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn finds_library_items() {
+        let source = r"
+            advent_of_code::solution!(1);
+            use advent_of_code::grid::Grid;
+            use advent_of_code::{parse::numbers, template::Day, gcd};
+            advent_of_code::template::examples::check(DAY, 1, part_one);
+        ";
+
+        assert_eq!(
+            library_items(source),
+            BTreeSet::from(["gcd".into(), "grid".into(), "parse".into()])
+        );
+        assert!(library_items("advent_of_code::solution!(1);").is_empty());
+    }
+
+    #[test]
+    fn falls_back_to_lib_for_unknown_items() {
+        assert_eq!(library_files("not_a_module"), vec!["./src/lib.rs"]);
+    }
+
+    #[test]
+    fn hashes_solutions() {
+        let hash = source_hash(day!(1)).unwrap();
+        assert_eq!(source_hash(day!(1)).unwrap(), hash);
+        assert_ne!(source_hash(day!(2)).unwrap(), hash);
+    }
+}
@@ -0,0 +1,201 @@
+/// Summary statistics of benchmark samples.
+use std::{collections::HashMap, time::Duration};
+use tinyjson::JsonValue;
+
+/// z-score of the two-sided 95% confidence interval.
+const Z_95: f64 = 1.96;
+
+/// Samples outside of `[q1 - k * iqr, q3 + k * iqr]` are considered outliers (Tukey's fences).
+const OUTLIER_FENCE: f64 = 1.5;
+
+/// Statistics over the samples of a benchmarked part. All durations are in nanoseconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub samples: u128,
+    pub warmup: u128,
+    pub mean: f64,
+    pub median: f64,
+    pub min: f64,
+    pub max: f64,
+    pub stddev: f64,
+    /// Lower bound of the 95% confidence interval of the mean.
+    pub ci_low: f64,
+    /// Upper bound of the 95% confidence interval of the mean.
+    pub ci_high: f64,
+    pub outliers: u128,
+}
+
+impl Stats {
+    /// Compute statistics from a set of timed samples.
+    /// Returns `None` if no samples were taken.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_samples(samples: &[Duration], warmup: u128) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        let n = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / n;
+
+        let variance = if nanos.len() > 1 {
+            nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+        let stddev = variance.sqrt();
+        let margin = Z_95 * stddev / n.sqrt();
+
+        let q1 = quantile(&nanos, 0.25);
+        let q3 = quantile(&nanos, 0.75);
+        let fence = OUTLIER_FENCE * (q3 - q1);
+
+        Some(Stats {
+            samples: nanos.len() as u128,
+            warmup,
+            mean,
+            median: quantile(&nanos, 0.5),
+            min: nanos[0],
+            max: nanos[nanos.len() - 1],
+            stddev,
+            ci_low: mean - margin,
+            ci_high: mean + margin,
+            outliers: nanos
+                .iter()
+                .filter(|x| **x < q1 - fence || **x > q3 + fence)
+                .count() as u128,
+        })
+    }
+}
+
+/// Linearly interpolated quantile of sorted values.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let pos = q * (sorted.len() - 1) as f64;
+    let lower = pos.floor() as usize;
+    let upper = pos.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (pos - lower as f64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Stats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("warmup".into(), JsonValue::Number(value.warmup as f64));
+        map.insert("mean".into(), JsonValue::Number(value.mean));
+        map.insert("median".into(), JsonValue::Number(value.median));
+        map.insert("min".into(), JsonValue::Number(value.min));
+        map.insert("max".into(), JsonValue::Number(value.max));
+        map.insert("stddev".into(), JsonValue::Number(value.stddev));
+        map.insert("ci_low".into(), JsonValue::Number(value.ci_low));
+        map.insert("ci_high".into(), JsonValue::Number(value.ci_high));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        Ok(Stats {
+            samples: number("samples")? as u128,
+            warmup: number("warmup")? as u128,
+            mean: number("mean")?,
+            median: number("median")?,
+            min: number("min")?,
+            max: number("max")?,
+            stddev: number("stddev")?,
+            ci_low: number("ci_low")?,
+            ci_high: number("ci_high")?,
+            outliers: number("outliers")? as u128,
+        })
+    }
+}
+
+/// Read an optional stats value, treating a missing key the same as `null`.
+pub fn optional_from_json(
+    json: &HashMap<String, JsonValue>,
+    key: &str,
+) -> Result<Option<Stats>, String> {
+    match json.get(key) {
+        None => Ok(None),
+        Some(v) if v.is_null() => Ok(None),
+        Some(v) => Stats::try_from(v).map(Some),
+    }
+}
+
+/// Serialize an optional stats value, using `null` for `None`.
+pub fn optional_to_json(stats: Option<&Stats>) -> JsonValue {
+    stats.map_or(JsonValue::Null, JsonValue::from)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Stats;
+    use std::time::Duration;
+
+    fn samples(nanos: &[u64]) -> Vec<Duration> {
+        nanos.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(Stats::from_samples(&[], 0), None);
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = Stats::from_samples(&samples(&[42]), 0).unwrap();
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.median, 42.0);
+        assert_eq!(stats.stddev, 0.0);
+        assert_eq!(stats.ci_low, 42.0);
+        assert_eq!(stats.ci_high, 42.0);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn computes_summary() {
+        let stats = Stats::from_samples(&samples(&[40, 10, 30, 20]), 5).unwrap();
+        assert_eq!(stats.samples, 4);
+        assert_eq!(stats.warmup, 5);
+        assert_eq!(stats.mean, 25.0);
+        assert_eq!(stats.median, 25.0);
+        assert_eq!(stats.min, 10.0);
+        assert_eq!(stats.max, 40.0);
+        assert!((stats.stddev - 12.909_944).abs() < 1e-6);
+        assert!(stats.ci_low < stats.mean && stats.ci_high > stats.mean);
+    }
+
+    #[test]
+    fn flags_outliers() {
+        let stats = Stats::from_samples(&samples(&[10, 11, 10, 12, 11, 10, 500]), 0).unwrap();
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.median, 11.0);
+    }
+}
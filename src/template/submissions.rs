@@ -0,0 +1,494 @@
+/// History of submitted answers and the verdicts Advent of Code returned for them.
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+    time::Duration,
+};
+
+use regex::Regex;
+use tinyjson::JsonValue;
+
+use crate::template::{timings::to_sorted_string, Day};
+
+static SUBMISSIONS_FILE_PATH: &str = "./data/submissions.json";
+
+/// Verdict of a submission, parsed from the response of Advent of Code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// The answer is wrong, without a hint in which direction.
+    Wrong,
+    /// The answer was not checked because an answer was submitted too recently.
+    RateLimited,
+    /// The part was solved before, so the answer was not checked.
+    AlreadySolved,
+    /// The response could not be parsed.
+    Unknown,
+}
+
+impl Verdict {
+    /// Whether the answer was checked and is known to be wrong.
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let verdict = match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Wrong => "wrong",
+            Verdict::RateLimited => "rate_limited",
+            Verdict::AlreadySolved => "already_solved",
+            Verdict::Unknown => "unknown",
+        };
+        write!(f, "{verdict}")
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too_high" => Ok(Verdict::TooHigh),
+            "too_low" => Ok(Verdict::TooLow),
+            "wrong" => Ok(Verdict::Wrong),
+            "rate_limited" => Ok(Verdict::RateLimited),
+            "already_solved" => Ok(Verdict::AlreadySolved),
+            "unknown" => Ok(Verdict::Unknown),
+            _ => Err(format!("`{s}` is not a valid verdict.")),
+        }
+    }
+}
+
+/// Parse the verdict and the time to wait before the next submission from a response.
+pub fn parse_response(response: &str) -> (Verdict, Option<Duration>) {
+    let verdict = if response.contains("That's the right answer") {
+        Verdict::Correct
+    } else if response.contains("You gave an answer too recently") {
+        Verdict::RateLimited
+    } else if response.contains("You don't seem to be solving the right level") {
+        Verdict::AlreadySolved
+    } else if response.contains("That's not the right answer") {
+        if response.contains("too high") {
+            Verdict::TooHigh
+        } else if response.contains("too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else {
+        Verdict::Unknown
+    };
+
+    (verdict, parse_wait(response))
+}
+
+fn parse_wait(response: &str) -> Option<Duration> {
+    let left = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+    let minutes = Regex::new(r"(?i)please wait (one|\d+) minutes?").unwrap();
+
+    if let Some(c) = left.captures(response) {
+        let m: u64 = c.get(1).map_or(Ok(0), |m| m.as_str().parse()).ok()?;
+        let s: u64 = c[2].parse().ok()?;
+        return Some(Duration::from_secs(m * 60 + s));
+    }
+
+    minutes.captures(response).and_then(|c| {
+        let m: u64 = match &c[1] {
+            "one" => 1,
+            x => x.parse().ok()?,
+        };
+        Some(Duration::from_secs(m * 60))
+    })
+}
+
+/// A submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Time to wait before the next submission, if the response asked for it.
+    pub cooldown: Option<Duration>,
+}
+
+/// Reason for not submitting a candidate answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rejection {
+    AlreadySolved {
+        answer: String,
+    },
+    KnownWrong,
+    /// The answer is at least as high as an answer that was too high.
+    TooHigh {
+        bound: i128,
+    },
+    /// The answer is at most as low as an answer that was too low.
+    TooLow {
+        bound: i128,
+    },
+    CoolingDown {
+        remaining: Duration,
+    },
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::AlreadySolved { answer } => {
+                write!(f, "this part was already solved with {answer}.")
+            }
+            Rejection::KnownWrong => write!(f, "this answer was already submitted and is wrong."),
+            Rejection::TooHigh { bound } => {
+                write!(f, "{bound} was already too high, so this answer is too.")
+            }
+            Rejection::TooLow { bound } => {
+                write!(f, "{bound} was already too low, so this answer is too.")
+            }
+            Rejection::CoolingDown { remaining } => write!(
+                f,
+                "Advent of Code asked to wait before submitting again. Try again in {}.",
+                format_wait(*remaining)
+            ),
+        }
+    }
+}
+
+/// Format a wait time like Advent of Code does, e.g. `4m 23s`.
+pub fn format_wait(wait: Duration) -> String {
+    let secs = wait.as_secs();
+    match secs / 60 {
+        0 => format!("{secs}s"),
+        m => format!("{m}m {}s", secs % 60),
+    }
+}
+
+/// Submitted answers, keyed by day.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Submissions {
+    pub data: BTreeMap<Day, Vec<Submission>>,
+}
+
+impl Submissions {
+    /// Dehydrate submissions to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        fs::write(SUBMISSIONS_FILE_PATH, to_sorted_string(&JsonValue::from(self)))
+    }
+
+    /// Rehydrate submissions from a JSON file. If not present, returns no submissions.
+    /// Fails if the file exists but can not be read or parsed, so that it is never overwritten.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(SUBMISSIONS_FILE_PATH) {
+            Ok(json) => Submissions::try_from(json)
+                .map_err(|e| format!("invalid {SUBMISSIONS_FILE_PATH}: {e}")),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("could not read {SUBMISSIONS_FILE_PATH}: {e}.")),
+        }
+    }
+
+    /// Record a submission of a day.
+    pub fn push(&mut self, day: Day, submission: Submission) {
+        self.data.entry(day).or_default().push(submission);
+    }
+
+    /// Check a candidate answer against previous submissions, at `now` seconds since the unix epoch.
+    pub fn check_candidate(
+        &self,
+        day: Day,
+        part: u8,
+        answer: &str,
+        now: u64,
+    ) -> Result<(), Rejection> {
+        // NOTE: the cooldown applies to all puzzles, so check the latest submission of any day.
+        if let Some(remaining) = self
+            .data
+            .values()
+            .flatten()
+            .max_by_key(|s| s.timestamp)
+            .and_then(|s| Some(s.timestamp + s.cooldown?.as_secs()))
+            .and_then(|until| until.checked_sub(now))
+            .filter(|remaining| *remaining > 0)
+        {
+            return Err(Rejection::CoolingDown {
+                remaining: Duration::from_secs(remaining),
+            });
+        }
+
+        let submissions: Vec<&Submission> = self
+            .data
+            .get(&day)
+            .into_iter()
+            .flatten()
+            .filter(|s| s.part == part)
+            .collect();
+
+        if let Some(correct) = submissions.iter().find(|s| s.verdict == Verdict::Correct) {
+            return Err(Rejection::AlreadySolved {
+                answer: correct.answer.clone(),
+            });
+        }
+
+        if submissions
+            .iter()
+            .any(|s| s.answer == answer && s.verdict.is_wrong())
+        {
+            return Err(Rejection::KnownWrong);
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bound = |verdict: Verdict| {
+            submissions
+                .iter()
+                .filter(move |s| s.verdict == verdict)
+                .filter_map(|s| s.answer.parse::<i128>().ok())
+        };
+
+        if let Some(bound) = bound(Verdict::TooHigh).min().filter(|b| value >= *b) {
+            return Err(Rejection::TooHigh { bound });
+        }
+
+        if let Some(bound) = bound(Verdict::TooLow).max().filter(|b| value <= *b) {
+            return Err(Rejection::TooLow { bound });
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submissions> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Submissions) -> Self {
+        let map: HashMap<String, JsonValue> = value
+            .data
+            .iter()
+            .map(|(day, submissions)| {
+                let submissions = submissions
+                    .iter()
+                    .map(|s| {
+                        let mut map: HashMap<String, JsonValue> = HashMap::new();
+                        map.insert("timestamp".into(), JsonValue::Number(s.timestamp as f64));
+                        map.insert("part".into(), JsonValue::Number(f64::from(s.part)));
+                        map.insert("answer".into(), JsonValue::String(s.answer.clone()));
+                        map.insert("verdict".into(), JsonValue::String(s.verdict.to_string()));
+                        map.insert(
+                            "cooldown".into(),
+                            s.cooldown
+                                .map_or(JsonValue::Null, |x| JsonValue::Number(x.as_secs() as f64)),
+                        );
+                        JsonValue::Object(map)
+                    })
+                    .collect();
+
+                (day.to_string(), JsonValue::Array(submissions))
+            })
+            .collect();
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_days = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let mut data = BTreeMap::new();
+
+        for (key, value) in json_days {
+            let day = Day::from_str(key).map_err(|_| format!("`{key}` is not a valid day."))?;
+
+            let json_submissions = value
+                .get::<Vec<JsonValue>>()
+                .ok_or(format!("expected submissions of day {day} to be an array."))?;
+
+            let mut submissions = vec![];
+
+            for json_submission in json_submissions {
+                let map = json_submission
+                    .get::<HashMap<String, JsonValue>>()
+                    .ok_or(format!("expected submission of day {day} to be an object."))?;
+
+                let number = |key: &str| {
+                    map.get(key)
+                        .and_then(|v| v.get::<f64>().copied())
+                        .ok_or(format!("expected {day}.{key} to be a number."))
+                };
+
+                let string = |key: &str| {
+                    map.get(key)
+                        .and_then(|v| v.get::<String>().cloned())
+                        .ok_or(format!("expected {day}.{key} to be a string."))
+                };
+
+                submissions.push(Submission {
+                    timestamp: number("timestamp")? as u64,
+                    part: number("part")? as u8,
+                    answer: string("answer")?,
+                    verdict: Verdict::from_str(&string("verdict")?)?,
+                    cooldown: map
+                        .get("cooldown")
+                        .and_then(|v| v.get::<f64>())
+                        .map(|x| Duration::from_secs(*x as u64)),
+                });
+            }
+
+            data.insert(day, submissions);
+        }
+
+        Ok(Submissions { data })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_response, Rejection, Submission, Submissions, Verdict};
+    use crate::day;
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    fn submission(timestamp: u64, answer: &str, verdict: Verdict) -> Submission {
+        Submission {
+            timestamp,
+            part: 1,
+            answer: answer.into(),
+            verdict,
+            cooldown: None,
+        }
+    }
+
+    fn get_mock_submissions() -> Submissions {
+        let mut submissions = Submissions::default();
+        submissions.push(day!(1), submission(100, "500", Verdict::TooHigh));
+        submissions.push(day!(1), submission(200, "100", Verdict::TooLow));
+        submissions.push(day!(1), submission(300, "300", Verdict::Wrong));
+        submissions
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            parse_response("That's the right answer! You are one gold star closer."),
+            (Verdict::Correct, None)
+        );
+        assert_eq!(
+            parse_response("That's not the right answer; your answer is too high. Please wait one minute before trying again."),
+            (Verdict::TooHigh, Some(Duration::from_secs(60)))
+        );
+        assert_eq!(
+            parse_response("That's not the right answer; your answer is too low. Please wait 5 minutes before trying again."),
+            (Verdict::TooLow, Some(Duration::from_secs(300)))
+        );
+        assert_eq!(
+            parse_response("That's not the right answer. If you're stuck, make sure you're using the full input data."),
+            (Verdict::Wrong, None)
+        );
+        assert_eq!(
+            parse_response("You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 4m 23s left to wait."),
+            (Verdict::RateLimited, Some(Duration::from_secs(263)))
+        );
+        assert_eq!(
+            parse_response("You gave an answer too recently. You have 35s left to wait."),
+            (Verdict::RateLimited, Some(Duration::from_secs(35)))
+        );
+        assert_eq!(
+            parse_response(
+                "You don't seem to be solving the right level. Did you already complete it?"
+            ),
+            (Verdict::AlreadySolved, None)
+        );
+        assert_eq!(parse_response(""), (Verdict::Unknown, None));
+    }
+
+    #[test]
+    fn rejects_known_wrong_answers() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check_candidate(day!(1), 1, "300", 1000),
+            Err(Rejection::KnownWrong)
+        );
+        assert_eq!(submissions.check_candidate(day!(1), 1, "200", 1000), Ok(()));
+        assert_eq!(submissions.check_candidate(day!(1), 2, "300", 1000), Ok(()));
+        assert_eq!(submissions.check_candidate(day!(2), 1, "300", 1000), Ok(()));
+    }
+
+    #[test]
+    fn rejects_answers_out_of_bounds() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check_candidate(day!(1), 1, "600", 1000),
+            Err(Rejection::TooHigh { bound: 500 })
+        );
+        assert_eq!(
+            submissions.check_candidate(day!(1), 1, "50", 1000),
+            Err(Rejection::TooLow { bound: 100 })
+        );
+        assert_eq!(submissions.check_candidate(day!(1), 1, "abc", 1000), Ok(()));
+    }
+
+    #[test]
+    fn rejects_solved_parts_and_cooldowns() {
+        let mut submissions = get_mock_submissions();
+        submissions.push(
+            day!(2),
+            Submission {
+                cooldown: Some(Duration::from_secs(60)),
+                ..submission(400, "1", Verdict::Wrong)
+            },
+        );
+        assert_eq!(
+            submissions.check_candidate(day!(1), 1, "200", 430),
+            Err(Rejection::CoolingDown {
+                remaining: Duration::from_secs(30)
+            })
+        );
+        assert_eq!(submissions.check_candidate(day!(1), 1, "200", 460), Ok(()));
+
+        submissions.push(day!(1), submission(500, "250", Verdict::Correct));
+        assert_eq!(
+            submissions.check_candidate(day!(1), 1, "250", 1000),
+            Err(Rejection::AlreadySolved {
+                answer: "250".into()
+            })
+        );
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let mut submissions = get_mock_submissions();
+        submissions.push(
+            day!(1),
+            Submission {
+                cooldown: Some(Duration::from_secs(263)),
+                ..submission(400, "200", Verdict::RateLimited)
+            },
+        );
+        let json = JsonValue::from(&submissions).stringify().unwrap();
+        assert_eq!(Submissions::try_from(json).unwrap(), submissions);
+    }
+}
@@ -0,0 +1,200 @@
+/// Module that prints an overview table of the parts run by `run_multi`.
+use std::time::Duration;
+
+use crate::template::{
+    report::{Report, Status},
+    Day, ANSI_BOLD, ANSI_RESET,
+};
+
+/// Answers longer than this are truncated in the table.
+const MAX_ANSWER_WIDTH: usize = 24;
+
+/// Overall status of a day, derived from the reports of its parts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DayStatus {
+    Solved,
+    Unsolved,
+    Failed,
+    WrongAnswer,
+}
+
+impl DayStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            DayStatus::Solved => "solved",
+            DayStatus::Unsolved => "unsolved",
+            DayStatus::Failed => "failed",
+            DayStatus::WrongAnswer => "wrong answer",
+        }
+    }
+}
+
+/// A day is failed if any part failed, wrong if any answer is wrong, and unsolved if any part is missing an answer.
+pub fn day_status(reports: &[&Report]) -> DayStatus {
+    if reports.iter().any(|r| r.status == Status::Failed) {
+        DayStatus::Failed
+    } else if reports.iter().any(|r| r.correct == Some(false)) {
+        DayStatus::WrongAnswer
+    } else if reports
+        .iter()
+        .any(|r| r.part != 0 && r.status == Status::Unsolved)
+    {
+        DayStatus::Unsolved
+    } else {
+        DayStatus::Solved
+    }
+}
+
+fn format_part(report: Option<&Report>) -> String {
+    let Some(report) = report else {
+        return "-".into();
+    };
+
+    match (&report.status, &report.answer) {
+        (Status::Failed, _) => "✖".into(),
+        (_, None) => "-".into(),
+        (_, Some(answer)) => {
+            let mut cell = if answer.contains('\n') {
+                "▼".to_string()
+            } else if answer.chars().count() > MAX_ANSWER_WIDTH {
+                let truncated: String = answer.chars().take(MAX_ANSWER_WIDTH - 1).collect();
+                format!("{truncated}…")
+            } else {
+                answer.clone()
+            };
+
+            match report.correct {
+                Some(true) => cell.push_str(" ✓"),
+                Some(false) => cell.push_str(" ✗"),
+                None => {}
+            }
+
+            cell
+        }
+    }
+}
+
+fn construct_table(reports: &[Report]) -> Vec<String> {
+    let mut days: Vec<Day> = reports.iter().map(|r| r.day).collect();
+    days.dedup();
+
+    let mut rows: Vec<[String; 5]> = vec![[
+        "Day".into(),
+        "Part 1".into(),
+        "Part 2".into(),
+        "Time".into(),
+        "Status".into(),
+    ]];
+
+    for day in days {
+        let day_reports: Vec<&Report> = reports.iter().filter(|r| r.day == day).collect();
+        let part = |part: u8| day_reports.iter().find(|r| r.part == part).copied();
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let nanos = day_reports.iter().map(|r| r.duration_nanos).sum::<f64>() as u64;
+
+        rows.push([
+            day.to_string(),
+            format_part(part(1)),
+            format_part(part(2)),
+            format!("{:.1?}", Duration::from_nanos(nanos)),
+            day_status(&day_reports).as_str().into(),
+        ]);
+    }
+
+    let widths: Vec<usize> = (0..5)
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect::<Vec<_>>()
+                .join(" | ")
+                .trim_end()
+                .to_string()
+        })
+        .collect()
+}
+
+/// Print an overview table with one row per day that was run.
+pub fn print(reports: &[Report]) {
+    if reports.is_empty() {
+        return;
+    }
+
+    println!("\n{ANSI_BOLD}Summary{ANSI_RESET}");
+    for line in construct_table(reports) {
+        println!("{line}");
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{construct_table, day_status, DayStatus};
+    use crate::{
+        day,
+        template::report::{Report, Status},
+    };
+
+    fn report(day: u8, part: u8, answer: Option<&str>, correct: Option<bool>) -> Report {
+        Report {
+            day: crate::template::Day::new(day).unwrap(),
+            part,
+            answer: answer.map(Into::into),
+            correct,
+            duration_nanos: 1_000.0,
+            samples: 1,
+            stats: None,
+            alloc: None,
+            status: if answer.is_some() {
+                Status::Solved
+            } else {
+                Status::Unsolved
+            },
+            error: None,
+        }
+    }
+
+    #[test]
+    fn derives_day_status() {
+        let solved = report(1, 1, Some("1"), None);
+        let unsolved = report(1, 2, None, None);
+        let wrong = report(1, 2, Some("2"), Some(false));
+        let failed = Report::failed(day!(1), 2, "panicked");
+
+        assert_eq!(day_status(&[&solved]), DayStatus::Solved);
+        assert_eq!(day_status(&[&solved, &unsolved]), DayStatus::Unsolved);
+        assert_eq!(day_status(&[&solved, &wrong]), DayStatus::WrongAnswer);
+        assert_eq!(day_status(&[&wrong, &failed]), DayStatus::Failed);
+    }
+
+    #[test]
+    fn constructs_table() {
+        let reports = vec![
+            report(1, 1, Some("11"), Some(true)),
+            report(1, 2, Some("31"), None),
+            report(2, 1, Some("a\nb"), None),
+            report(2, 2, None, None),
+            Report::failed(day!(3), 1, "panicked"),
+            report(3, 2, Some("123456789012345678901234567890"), Some(false)),
+        ];
+
+        assert_eq!(
+            construct_table(&reports),
+            [
+                "Day | Part 1 | Part 2                     | Time  | Status",
+                "01  | 11 ✓   | 31                         | 2.0µs | solved",
+                "02  | ▼      | -                          | 2.0µs | unsolved",
+                "03  | ✖      | 12345678901234567890123… ✗ | 1.0µs | failed",
+            ]
+        );
+    }
+}
@@ -1,17 +1,190 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{alloc, alloc::AllocStats, stats, stats::Stats, Day};
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
+/// Version of the `timings.json` schema written by this template.
+/// Files without a version are the original string-based format and are upgraded on read.
+pub const TIMINGS_VERSION: u32 = 1;
+
+/// Benchmark time of the parse step or of a single part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartTiming {
+    /// Median execution time in nanoseconds.
+    pub nanos: f64,
+    /// Number of timed samples the median was taken from.
+    pub samples: u128,
+    pub stats: Option<Stats>,
+    /// Heap usage, present if the solution was benched with `cargo time --memory`.
+    pub alloc: Option<AllocStats>,
+}
+
+impl Display for PartTiming {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format_nanos(self.nanos))
+    }
+}
+
+/// Format nanoseconds like `{:.1?}` formats a `Duration`, but keep a second decimal if there is one,
+/// so that sub-unit precision like `74.13ns` is not lost.
+fn format_nanos(nanos: f64) -> String {
+    let (value, unit) = [(1.0, "ns"), (1e3, "µs"), (1e6, "ms")]
+        .into_iter()
+        .map(|(factor, unit)| (nanos / factor, unit))
+        .find(|(value, _)| (value * 100.0).round() < 100_000.0)
+        .unwrap_or((nanos / 1e9, "s"));
+
+    let value = format!("{value:.2}");
+    let value = value.strip_suffix('0').unwrap_or(&value);
+    format!("{value}{unit}")
+}
+
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
-    pub total_nanos: f64,
+    /// Time of the shared parse step, if the solution parses its input once for both parts.
+    pub parse: Option<PartTiming>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
+    /// Hash of the day's source and the library code it uses when it was benched.
+    pub source_hash: Option<String>,
+}
+
+impl Timing {
+    /// Timing of a part, where part `0` is the parse step.
+    pub fn part(&self, part: u8) -> Option<&PartTiming> {
+        match part {
+            0 => self.parse.as_ref(),
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Merge a new timing of the same day into this one, part by part.
+    /// `history` holds earlier runs, oldest first, and is only used by [`MergePolicy::Median`].
+    fn merge(&self, new: &Timing, policy: MergePolicy, history: &[&Timings]) -> Timing {
+        // timings of a different source are not comparable, so the new measurement wins.
+        let policy = match policy {
+            MergePolicy::Best if new.source_hash != self.source_hash => MergePolicy::Latest,
+            policy => policy,
+        };
+
+        let part = |part: u8| {
+            let previous = history
+                .iter()
+                .rev()
+                .filter_map(|t| t.data.iter().find(|x| x.day == self.day))
+                .filter(|t| t.source_hash == new.source_hash)
+                .filter_map(|t| t.part(part));
+            merge_part(self.part(part), new.part(part), policy, previous)
+        };
+
+        // a solution without a separate parse step reports its parts, but no parse time.
+        let parse = if new.parse.is_none() && (new.part_1.is_some() || new.part_2.is_some()) {
+            None
+        } else {
+            part(0)
+        };
+
+        let mut merged = Timing {
+            day: self.day,
+            parse,
+            part_1: part(1),
+            part_2: part(2),
+            source_hash: None,
+        };
+
+        // the new hash only describes the day if every part that keeps a timing was re-measured.
+        let remeasured = (0..=2).all(|x| merged.part(x).is_none() || new.part(x).is_some());
+        merged.source_hash = if remeasured {
+            new.source_hash.clone().or_else(|| self.source_hash.clone())
+        } else if new.source_hash.is_none() || new.source_hash == self.source_hash {
+            self.source_hash.clone()
+        } else {
+            None
+        };
+
+        merged
+    }
+
+    /// Sum of the parse step and both parts in nanoseconds.
+    pub fn total_nanos(&self) -> f64 {
+        [&self.parse, &self.part_1, &self.part_2]
+            .into_iter()
+            .flatten()
+            .map(|x| x.nanos)
+            .sum()
+    }
+}
+
+/// How a newly benched part is merged with the stored timing of the same part.
+/// Parts that were not benched in the new run always keep their stored timing.
+/// Only measurements of the same source are compared, so a changed solution always takes its new timing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// Keep the latest measurement.
+    #[default]
+    Latest,
+    /// Keep the fastest measurement.
+    Best,
+    /// Keep the measurement with the median time among the last `n` runs, including the new one.
+    Median(usize),
+}
+
+impl FromStr for MergePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "latest" => Ok(Self::Latest),
+            None if s == "best" => Ok(Self::Best),
+            None if s == "median" => Ok(Self::Median(5)),
+            Some(("median", n)) => match n.parse::<usize>() {
+                Ok(n) if n > 0 => Ok(Self::Median(n)),
+                _ => Err(format!("`{n}` is not a valid number of runs.")),
+            },
+            _ => Err(format!(
+                "unknown policy `{s}`, expected `latest`, `best` or `median[:<runs>]`."
+            )),
+        }
+    }
+}
+
+fn merge_part<'a>(
+    stored: Option<&PartTiming>,
+    new: Option<&'a PartTiming>,
+    policy: MergePolicy,
+    previous: impl Iterator<Item = &'a PartTiming>,
+) -> Option<PartTiming> {
+    let Some(new) = new else {
+        return stored.cloned();
+    };
+
+    match policy {
+        MergePolicy::Latest => Some(new.clone()),
+        MergePolicy::Best => Some(
+            stored
+                .filter(|x| x.nanos < new.nanos)
+                .unwrap_or(new)
+                .clone(),
+        ),
+        MergePolicy::Median(n) => {
+            let mut runs: Vec<&PartTiming> = std::iter::once(new).chain(previous).take(n).collect();
+            runs.sort_by(|a, b| a.nanos.total_cmp(&b.nanos));
+            // for an even number of runs, keep the faster of the two middle runs.
+            Some(runs[(runs.len() - 1) / 2].clone())
+        }
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -22,27 +195,45 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
+    /// Dehydrate timings to a JSON file, with keys sorted so that diffs stay minimal.
     pub fn store_file(&self) -> Result<(), Error> {
-        let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
-        json.format_to(&mut file)
+        fs::write(
+            TIMINGS_FILE_PATH,
+            to_sorted_string(&JsonValue::from(self.clone())),
+        )
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
-            .unwrap_or_default()
+    /// Fails if the file exists but can not be read or parsed, so that it is never overwritten.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(TIMINGS_FILE_PATH) {
+            Ok(json) => {
+                Timings::try_from(json).map_err(|e| format!("invalid {TIMINGS_FILE_PATH}: {e}"))
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("could not read {TIMINGS_FILE_PATH}: {e}.")),
+        }
     }
 
-    /// Merge two sets of timings, overwriting `self` with `other` if present.
-    pub fn merge(&self, new: &Self) -> Self {
+    /// Merge a new set of timings into `self` part by part, resolving parts that were benched again with `policy`.
+    /// `history` holds earlier runs, oldest first, and is only used by [`MergePolicy::Median`].
+    /// It should only contain runs measured on the current machine.
+    pub fn merge(&self, new: &Self, policy: MergePolicy, history: &[&Timings]) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let stored = self.data.iter().find(|t| t.day == timing.day);
+            data.push(match stored {
+                Some(stored) => stored.merge(timing, policy, history),
+                None => Timing {
+                    day: timing.day,
+                    parse: None,
+                    part_1: None,
+                    part_2: None,
+                    source_hash: None,
+                }
+                .merge(timing, policy, history),
+            });
         }
 
         for timing in &self.data {
@@ -51,13 +242,42 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
+    /// Attach the heap usage measured by an instrumented run to the stored parts it covers.
+    /// Runtimes of an instrumented run are not comparable, so only days whose stored timings share
+    /// the source hash of the run are updated, and their runtimes are kept.
+    pub fn merge_alloc(&self, new: &Self) -> Self {
+        let mut merged = self.clone();
+
+        for timing in &mut merged.data {
+            let Some(new) = new
+                .data
+                .iter()
+                .find(|t| t.day == timing.day && t.source_hash == timing.source_hash)
+            else {
+                continue;
+            };
+
+            let parts = [&mut timing.parse, &mut timing.part_1, &mut timing.part_2];
+            for (stored, new) in parts
+                .into_iter()
+                .zip([&new.parse, &new.part_1, &new.part_2])
+            {
+                if let (Some(stored), Some(new)) = (stored, new) {
+                    stored.alloc = new.alloc;
+                }
+            }
+        }
+
+        merged
+    }
+
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
-        self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
+        self.data.iter().map(Timing::total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
     pub fn is_day_complete(&self, day: Day) -> bool {
@@ -69,10 +289,80 @@ impl Timings {
 
 /* -------------------------------------------------------------------------- */
 
+/// Pretty-print `value` for a version-controlled data file, with sorted keys and a trailing newline.
+pub(crate) fn to_sorted_string(value: &JsonValue) -> String {
+    let mut contents = String::new();
+    write_sorted(value, 0, &mut contents);
+    contents.push('\n');
+    contents
+}
+
+/// Pretty-print `value` like `JsonValue::format_to`, but with object keys in sorted order.
+fn write_sorted(value: &JsonValue, depth: usize, out: &mut String) {
+    let indent = |depth: usize| "  ".repeat(depth);
+
+    match value {
+        JsonValue::Object(map) if !map.is_empty() => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+
+            out.push_str("{\n");
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push_str(",\n");
+                }
+                out.push_str(&indent(depth + 1));
+                write_sorted(&JsonValue::String(key.clone()), depth + 1, out);
+                out.push_str(": ");
+                write_sorted(&map[key], depth + 1, out);
+            }
+            out.push('\n');
+            out.push_str(&indent(depth));
+            out.push('}');
+        }
+        JsonValue::Array(items) if !items.is_empty() => {
+            out.push_str("[\n");
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(",\n");
+                }
+                out.push_str(&indent(depth + 1));
+                write_sorted(item, depth + 1, out);
+            }
+            out.push('\n');
+            out.push_str(&indent(depth));
+            out.push(']');
+        }
+        // scalars and empty containers are printed as-is.
+        x => out.push_str(&x.stringify().unwrap_or_else(|_| "null".into())),
+    }
+}
+
+/// Parse a duration as printed by `{:.1?}`, e.g. `74.1µs` or `2.0s`, into nanoseconds.
+fn parse_duration(s: &str) -> Option<f64> {
+    let split = s.find(|c: char| c.is_alphabetic())?;
+    let (value, unit) = s.split_at(split);
+    let value = value.trim().parse::<f64>().ok()?;
+
+    let factor = match unit.trim() {
+        "ns" => 1.0,
+        "µs" | "us" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => return None,
+    };
+
+    Some(value * factor)
+}
+
+/* -------------------------------------------------------------------------- */
+
 impl From<Timings> for JsonValue {
+    #[allow(clippy::cast_lossless)]
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert("version".into(), JsonValue::Number(TIMINGS_VERSION as f64));
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
@@ -88,9 +378,25 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        // NOTE: files written before the schema was versioned have no `version` key.
+        let version = match json.get("version") {
+            None => 0.0,
+            Some(v) => *v
+                .get::<f64>()
+                .ok_or("expected `json.version` to be a number.")?,
+        };
+
+        if version > f64::from(TIMINGS_VERSION) {
+            return Err(format!(
+                "timings were stored with schema version {version}, but only versions up to {TIMINGS_VERSION} are supported."
+            ));
+        }
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
@@ -107,30 +413,97 @@ impl TryFrom<String> for Timings {
 
 /* -------------------------------------------------------------------------- */
 
-impl From<&Timing> for JsonValue {
-    fn from(value: &Timing) -> Self {
+impl From<&PartTiming> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &PartTiming) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
-        map.insert("day".into(), JsonValue::String(value.day.to_string()));
-        map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
-
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
-
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
         map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "stats".into(),
+            stats::optional_to_json(value.stats.as_ref()),
         );
+        map.insert(
+            "alloc".into(),
+            alloc::optional_to_json(value.alloc.as_ref()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
 
+impl TryFrom<&JsonValue> for PartTiming {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part timing to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected timing.{key} to be a number."))
+        };
+
+        Ok(PartTiming {
+            nanos: number("nanos")?,
+            samples: number("samples")? as u128,
+            stats: stats::optional_from_json(json, "stats")?,
+            alloc: alloc::optional_from_json(json, "alloc")?,
+        })
+    }
+}
+
+/// Read the timing of the parse step or a part.
+/// Timings stored before the schema was versioned hold a display string like `74.1µs` plus
+/// a separate `<key>_stats` entry, which are upgraded here.
+fn part_from_json(
+    json: &HashMap<String, JsonValue>,
+    key: &str,
+) -> Result<Option<PartTiming>, String> {
+    match json.get(key) {
+        None => Ok(None),
+        Some(v) if v.is_null() => Ok(None),
+        Some(JsonValue::String(s)) => {
+            let stats = stats::optional_from_json(json, &format!("{key}_stats"))?;
+            let nanos = match &stats {
+                // the display string was rounded from the median, so prefer the exact value.
+                Some(stats) => stats.median,
+                None => parse_duration(s).ok_or(format!(
+                    "Expected timing.{key} to be a duration, got `{s}`."
+                ))?,
+            };
+
+            Ok(Some(PartTiming {
+                nanos,
+                samples: stats.as_ref().map_or(1, |x| x.samples),
+                stats,
+                alloc: None,
+            }))
+        }
+        Some(v) => PartTiming::try_from(v).map(Some),
+    }
+}
+
+impl From<&Timing> for JsonValue {
+    fn from(value: &Timing) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let part = |x: &Option<PartTiming>| x.as_ref().map_or(JsonValue::Null, JsonValue::from);
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("parse".into(), part(&value.parse));
+        map.insert("part_1".into(), part(&value.part_1));
+        map.insert("part_2".into(), part(&value.part_2));
         map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "source_hash".into(),
+            value
+                .source_hash
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
         );
 
         JsonValue::Object(map)
@@ -151,26 +524,28 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
-
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
-
-        let total_nanos = json
-            .get("total_nanos")
-            .and_then(|v| v.get::<f64>().copied())
-            .ok_or("Expected timing.total_nanos to be a number.")?;
+        // NOTE: both parts have always been stored, `parse` is optional to support
+        // timings stored before it was introduced.
+        for key in ["part_1", "part_2"] {
+            if !json.contains_key(key) {
+                return Err(format!("Expected timing.{key} to be present."));
+            }
+        }
 
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
-            total_nanos,
+            parse: part_from_json(json, "parse")?,
+            part_1: part_from_json(json, "part_1")?,
+            part_2: part_from_json(json, "part_2")?,
+            source_hash: match json.get("source_hash") {
+                None => None,
+                Some(v) if v.is_null() => None,
+                Some(v) => Some(
+                    v.get::<String>()
+                        .ok_or("Expected timing.source_hash to be null or string.")?
+                        .clone(),
+                ),
+            },
         })
     }
 }
@@ -181,28 +556,40 @@ impl TryFrom<&JsonValue> for Timing {
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{PartTiming, Timing, Timings};
+
+    fn part(nanos: f64) -> Option<PartTiming> {
+        Some(PartTiming {
+            nanos,
+            samples: 10,
+            stats: None,
+            alloc: None,
+        })
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    parse: None,
+                    part_1: part(1e10),
+                    part_2: part(2e10),
+                    source_hash: None,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    parse: None,
+                    part_1: part(3e10),
+                    part_2: part(4e10),
+                    source_hash: None,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    parse: None,
+                    part_1: part(4e10),
                     part_2: None,
-                    total_nanos: 4e+10,
+                    source_hash: None,
                 },
             ],
         }
@@ -213,14 +600,47 @@ mod tests {
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "version": 1, "data": [{ "day": "01", "parse": null, "part_1": { "nanos": 1000000, "samples": 10, "stats": null }, "part_2": null }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            let part_1 = timing.part_1.as_ref().unwrap();
+            assert_eq!(part_1.nanos, 1_000_000_f64);
+            assert_eq!(part_1.samples, 10);
             assert_eq!(timing.part_2, None);
-            assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.total_nanos(), 1_000_000_f64);
+        }
+
+        #[test]
+        fn handles_json_timings_with_stats() {
+            let json = r#"{ "version": 1, "data": [{ "day": "01", "part_1": { "nanos": 1000000, "samples": 10, "stats": { "samples": 10, "warmup": 1, "mean": 1000000, "median": 1000000, "min": 900000, "max": 1100000, "stddev": 50000, "ci_low": 969000, "ci_high": 1031000, "outliers": 0 } }, "part_2": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1.as_ref().unwrap().stats.as_ref().unwrap();
+            assert_eq!(stats.samples, 10);
+            assert_eq!(stats.min, 900_000_f64);
+        }
+
+        #[test]
+        fn upgrades_unversioned_timings() {
+            let json = r#"{ "data": [{ "day": "01", "parse": "2.5µs", "part_1": "1ms", "part_2": null, "total_nanos": 1002500 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse.as_ref().unwrap().nanos, 2_500_f64);
+            assert_eq!(timing.part_1.as_ref().unwrap().nanos, 1_000_000_f64);
+            assert_eq!(timing.part_1.as_ref().unwrap().samples, 1);
+            assert_eq!(timing.part_2, None);
+        }
+
+        #[test]
+        fn upgrades_unversioned_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1.0ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "samples": 10, "warmup": 1, "mean": 1000000, "median": 1040000.5, "min": 900000, "max": 1100000, "stddev": 50000, "ci_low": 969000, "ci_high": 1031000, "outliers": 0 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let part_1 = timings.data[0].part_1.as_ref().unwrap();
+            assert_eq!(part_1.nanos, 1_040_000.5_f64);
+            assert_eq!(part_1.samples, 10);
+            assert_eq!(part_1.stats.as_ref().unwrap().min, 900_000_f64);
         }
 
         #[test]
@@ -243,10 +663,28 @@ mod tests {
             let json = r#"{ "data": [{ "day": "01" }, { "day": "26" }, { "day": "02", "part_2": null, "total_nanos": 0 }] }"#.to_string();
             Timings::try_from(json).unwrap();
         }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_newer_versions() {
+            let json = r#"{ "version": 99, "data": [] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
+    }
+
+    #[test]
+    fn formats_part_timings() {
+        let format = |nanos| part(nanos).unwrap().to_string();
+        assert_eq!(format(74.13), "74.13ns");
+        assert_eq!(format(595.0), "595.0ns");
+        assert_eq!(format(999.999), "1.0µs");
+        assert_eq!(format(74_100_000.0), "74.1ms");
+        assert_eq!(format(2e9), "2.0s");
     }
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::timings::{write_sorted, Timings};
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
@@ -254,11 +692,10 @@ mod tests {
         fn serializes_timings() {
             let timings = get_mock_timings();
             let value = JsonValue::try_from(timings).unwrap();
+            let json = value.get::<HashMap<String, JsonValue>>().unwrap();
+            assert_eq!(json.get("version").unwrap(), &JsonValue::Number(1.0));
             assert_eq!(
-                value
-                    .get::<HashMap<String, JsonValue>>()
-                    .unwrap()
-                    .get("data")
+                json.get("data")
                     .unwrap()
                     .get::<Vec<JsonValue>>()
                     .unwrap()
@@ -266,9 +703,49 @@ mod tests {
                 3
             );
         }
+
+        #[test]
+        fn writes_sorted_keys() {
+            let mut timings = get_mock_timings();
+            timings.data.truncate(1);
+
+            let mut out = String::new();
+            write_sorted(&JsonValue::from(timings.clone()), 0, &mut out);
+
+            let expected = [
+                "{",
+                "  \"data\": [",
+                "    {",
+                "      \"day\": \"01\",",
+                "      \"parse\": null,",
+                "      \"part_1\": {",
+                "        \"alloc\": null,",
+                "        \"nanos\": 10000000000,",
+                "        \"samples\": 10,",
+                "        \"stats\": null",
+                "      },",
+                "      \"part_2\": {",
+                "        \"alloc\": null,",
+                "        \"nanos\": 20000000000,",
+                "        \"samples\": 10,",
+                "        \"stats\": null",
+                "      },",
+                "      \"source_hash\": null",
+                "    }",
+                "  ],",
+                "  \"version\": 1",
+                "}",
+            ]
+            .join("\n");
+            assert_eq!(out, expected);
+
+            let parsed = Timings::try_from(out).unwrap();
+            assert_eq!(parsed.data[0].part_2, timings.data[0].part_2);
+        }
     }
 
     mod is_day_complete {
+        use super::part;
         use crate::{
             day,
             template::timings::{Timing, Timings},
@@ -279,13 +756,14 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
-                    total_nanos: 3_000_000_000_f64,
+                    parse: None,
+                    part_1: part(1e6),
+                    part_2: part(2e6),
+                    source_hash: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +771,14 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    parse: None,
+                    part_1: part(1e6),
                     part_2: None,
-                    total_nanos: 1_000_000_000_f64,
+                    source_hash: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -307,23 +786,43 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
-                    total_nanos: 0.0,
+                    source_hash: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                alloc::AllocStats,
+                timings::{MergePolicy, Timing, Timings},
+            },
         };
 
-        use super::get_mock_timings;
+        use super::{get_mock_timings, part};
+
+        fn single(part_1: f64, part_2: Option<f64>) -> Timings {
+            Timings {
+                data: vec![Timing {
+                    day: day!(1),
+                    parse: None,
+                    part_1: part(part_1),
+                    part_2: part_2.and_then(part),
+                    source_hash: None,
+                }],
+            }
+        }
+
+        fn nanos(timings: &Timings, n: u8) -> Option<f64> {
+            timings.data[0].part(n).map(|x| x.nanos)
+        }
 
         #[test]
         fn handles_disjunct_timings() {
@@ -331,12 +830,13 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
-                    total_nanos: 0_f64,
+                    source_hash: None,
                 }],
             };
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, MergePolicy::Latest, &[]);
             assert_eq!(merged.data.len(), 4);
             assert_eq!(merged.data[0].day, day!(1));
             assert_eq!(merged.data[1].day, day!(2));
@@ -351,25 +851,165 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
-                    total_nanos: 0_f64,
+                    source_hash: None,
                 }],
             };
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, MergePolicy::Latest, &[]);
 
             assert_eq!(merged.data.len(), 3);
             assert_eq!(merged.data[0].day, day!(1));
             assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[1].total_nanos, 0_f64);
+            assert_eq!(merged.data[1].total_nanos(), 7e10);
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_parts_missing_from_new_timings() {
+            let stored = single(100.0, Some(200.0));
+            let merged = stored.merge(&single(50.0, None), MergePolicy::Latest, &[]);
+            assert_eq!(nanos(&merged, 1), Some(50.0));
+            assert_eq!(nanos(&merged, 2), Some(200.0));
+        }
+
+        #[test]
+        fn keeps_source_hash_only_if_all_parts_were_remeasured() {
+            let with_hash = |timings: Timings, hash: &str| {
+                let mut timings = timings;
+                timings.data[0].source_hash = Some(hash.into());
+                timings
+            };
+            let hash = |timings: &Timings| timings.data[0].source_hash.clone();
+
+            let stored = with_hash(single(100.0, Some(200.0)), "a");
+
+            let merged = stored.merge(
+                &with_hash(single(50.0, None), "b"),
+                MergePolicy::Latest,
+                &[],
+            );
+            assert_eq!(nanos(&merged, 2), Some(200.0));
+            assert_eq!(hash(&merged), None);
+
+            let merged = stored.merge(
+                &with_hash(single(50.0, None), "a"),
+                MergePolicy::Latest,
+                &[],
+            );
+            assert_eq!(hash(&merged), Some("a".into()));
+
+            let merged = stored.merge(
+                &with_hash(single(50.0, Some(60.0)), "b"),
+                MergePolicy::Latest,
+                &[],
+            );
+            assert_eq!(hash(&merged), Some("b".into()));
+        }
+
+        #[test]
+        fn drops_parse_if_no_longer_reported() {
+            let mut stored = single(100.0, Some(200.0));
+            stored.data[0].parse = part(10.0);
+            let merged = stored.merge(&single(50.0, None), MergePolicy::Latest, &[]);
+            assert_eq!(nanos(&merged, 0), None);
+            let merged = stored.merge(&Timings::default(), MergePolicy::Latest, &[]);
+            assert_eq!(nanos(&merged, 0), Some(10.0));
+        }
+
+        #[test]
+        fn keeps_best_parts() {
+            let stored = single(100.0, Some(200.0));
+            let merged = stored.merge(&single(150.0, Some(150.0)), MergePolicy::Best, &[]);
+            assert_eq!(nanos(&merged, 1), Some(100.0));
+            assert_eq!(nanos(&merged, 2), Some(150.0));
+        }
+
+        #[test]
+        fn keeps_median_of_recent_runs() {
+            let history = [
+                single(10.0, None),
+                single(300.0, Some(400.0)),
+                single(200.0, None),
+            ];
+            let history: Vec<&Timings> = history.iter().collect();
+            let stored = single(200.0, Some(400.0));
+
+            let merged = stored.merge(
+                &single(500.0, Some(100.0)),
+                MergePolicy::Median(3),
+                &history,
+            );
+            // part 1: 500, 200, 300. part 2: 100, 400.
+            assert_eq!(nanos(&merged, 1), Some(300.0));
+            assert_eq!(nanos(&merged, 2), Some(100.0));
+
+            let merged = stored.merge(&single(500.0, None), MergePolicy::Median(1), &history);
+            assert_eq!(nanos(&merged, 1), Some(500.0));
+        }
+
+        #[test]
+        fn merges_only_alloc_of_matching_sources() {
+            let mut stored = single(100.0, None);
+            stored.data[0].source_hash = Some("a".into());
+
+            let mut new = single(500.0, Some(600.0));
+            new.data[0].source_hash = Some("a".into());
+            new.data[0].part_1.as_mut().unwrap().alloc = Some(AllocStats {
+                allocations: 1,
+                total_bytes: 64,
+                peak_bytes: 64,
+            });
+
+            let alloc = |timings: &Timings| timings.data[0].part_1.as_ref().unwrap().alloc;
+
+            let merged = stored.merge_alloc(&new);
+            assert_eq!(nanos(&merged, 1), Some(100.0));
+            assert_eq!(nanos(&merged, 2), None);
+            assert_eq!(alloc(&merged), alloc(&new));
+
+            new.data[0].source_hash = Some("b".into());
+            assert_eq!(alloc(&stored.merge_alloc(&new)), None);
+        }
+
+        #[test]
+        fn ignores_timings_of_other_sources() {
+            let with_hash = |part_1: f64, hash: &str| {
+                let mut timings = single(part_1, None);
+                timings.data[0].source_hash = Some(hash.into());
+                timings
+            };
+
+            let stored = with_hash(100.0, "a");
+            let merged = stored.merge(&with_hash(150.0, "b"), MergePolicy::Best, &[]);
+            assert_eq!(nanos(&merged, 1), Some(150.0));
+            let merged = stored.merge(&with_hash(150.0, "a"), MergePolicy::Best, &[]);
+            assert_eq!(nanos(&merged, 1), Some(100.0));
+
+            let history = [with_hash(10.0, "a"), with_hash(20.0, "a")];
+            let history: Vec<&Timings> = history.iter().collect();
+            let merged = stored.merge(&with_hash(500.0, "b"), MergePolicy::Median(3), &history);
+            assert_eq!(nanos(&merged, 1), Some(500.0));
+            let merged = stored.merge(&with_hash(500.0, "a"), MergePolicy::Median(3), &history);
+            assert_eq!(nanos(&merged, 1), Some(20.0));
+        }
+
+        #[test]
+        fn parses_policies() {
+            assert_eq!("latest".parse(), Ok(MergePolicy::Latest));
+            assert_eq!("best".parse(), Ok(MergePolicy::Best));
+            assert_eq!("median".parse(), Ok(MergePolicy::Median(5)));
+            assert_eq!("median:3".parse(), Ok(MergePolicy::Median(3)));
+            assert!("median:0".parse::<MergePolicy>().is_err());
+            assert!("fastest".parse::<MergePolicy>().is_err());
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
             let other = get_mock_timings();
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, MergePolicy::Latest, &[]);
             assert_eq!(merged.data.len(), 3);
         }
 
@@ -377,7 +1017,7 @@ mod tests {
         fn handles_empty_other_timings() {
             let timings = get_mock_timings();
             let other = Timings::default();
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, MergePolicy::Latest, &[]);
             assert_eq!(merged.data.len(), 3);
         }
     }